@@ -2,6 +2,8 @@
 package app.tauri.biometry
 
 import android.app.Activity
+import android.app.KeyguardManager
+import android.content.Context
 import android.content.Intent
 import android.content.pm.PackageManager
 import android.os.Build
@@ -30,6 +32,10 @@ import java.security.KeyPairGenerator
 import java.security.KeyStore
 import java.security.PrivateKey
 import java.security.PublicKey
+import java.security.Signature
+import java.security.interfaces.ECPublicKey
+import java.security.spec.ECGenParameterSpec
+import java.math.BigInteger
 import java.util.concurrent.Executor
 import javax.crypto.Cipher
 import javax.crypto.BadPaddingException
@@ -104,6 +110,31 @@ class RemoveDataOptions {
     lateinit var domain: String
 }
 
+@InvokeArg
+class RenameDataOptions {
+    lateinit var domain: String
+    lateinit var name: String
+    lateinit var newDomain: String
+    lateinit var newName: String
+    var reason: String? = null
+    var cancelTitle: String? = null
+}
+
+@InvokeArg
+class KeyOptions {
+    lateinit var domain: String
+    lateinit var name: String
+}
+
+@InvokeArg
+class KeyUseOptions {
+    lateinit var domain: String
+    lateinit var name: String
+    lateinit var data: String
+    lateinit var reason: String
+    var cancelTitle: String? = null
+}
+
 // Extension property to create DataStore instance
 private val Context.biometricDataStore: DataStore<Preferences> by preferencesDataStore(name = "biometric_data")
 
@@ -135,6 +166,8 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
         private const val MAX_DOMAIN_LEN = 64
         private const val MAX_NAME_LEN = 256
         private const val KEYSTORE_ALIAS_PREFIX = "biometry_"
+        private const val SIGNING_KEY_ALIAS_PREFIX = "biometry_key_"
+        private const val EC_COORDINATE_LEN = 32
         private val DOMAIN_PATTERN = Regex("^[A-Za-z0-9._-]+$")
 
         // Maps biometry error numbers to string error codes
@@ -153,7 +186,7 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
            biometryErrorCodeMap[BiometricPrompt.ERROR_NO_BIOMETRICS] = "biometryNotEnrolled"
            biometryErrorCodeMap[BiometricPrompt.ERROR_NO_DEVICE_CREDENTIAL] = "noDeviceCredential"
            biometryErrorCodeMap[BiometricPrompt.ERROR_NO_SPACE] = "systemCancel"
-           biometryErrorCodeMap[BiometricPrompt.ERROR_TIMEOUT] = "systemCancel"
+           biometryErrorCodeMap[BiometricPrompt.ERROR_TIMEOUT] = "timeout"
            biometryErrorCodeMap[BiometricPrompt.ERROR_UNABLE_TO_PROCESS] = "systemCancel"
            biometryErrorCodeMap[BiometricPrompt.ERROR_USER_CANCELED] = "userCancel"
            biometryErrorCodeMap[BiometricPrompt.ERROR_VENDOR] = "systemCancel"
@@ -204,7 +237,15 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
             available
         )
 
-        ret.put("biometryType", biometryTypes[0].type)
+        ret.put(
+            "biometryType",
+            if (biometryTypes.size > 1) BiometryType.MULTIPLE.type else biometryTypes[0].type
+        )
+        val modalities = JSArray()
+        if (available) {
+            biometryTypes.filter { it != BiometryType.NONE }.forEach { modalities.put(it.type) }
+        }
+        ret.put("modalities", modalities)
 
         if (!available) {
             var reason = ""
@@ -231,6 +272,26 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
             ret.put("errorCode", errorCode)
         }
 
+        // Records are wrapped by an auth-bound keystore key behind a
+        // CryptoObject prompt, which only Class 3 biometry can unlock.
+        val strong = manager.canAuthenticate(BiometricManager.Authenticators.BIOMETRIC_STRONG) ==
+            BiometricManager.BIOMETRIC_SUCCESS
+        val keyguard = activity.getSystemService(Context.KEYGUARD_SERVICE) as KeyguardManager
+        val capabilities = JSObject()
+        capabilities.put("storage", strong)
+        capabilities.put("deviceCredential", keyguard.isDeviceSecure)
+        capabilities.put(
+            "strength",
+            when {
+                strong -> "strong"
+                available -> "weak"
+                else -> "none"
+            }
+        )
+        capabilities.put("backend", "androidKeystore")
+        capabilities.put("backendVersion", Build.VERSION.SDK_INT.toString())
+        ret.put("capabilities", capabilities)
+
         invoke.resolve(ret)
     }
 
@@ -328,8 +389,9 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
         }
     }
 
+    // Values match the Rust and TypeScript `BiometryType`.
     internal enum class BiometryType(val type: Int) {
-        NONE(0), FINGERPRINT(1), FACE(2), IRIS(3);
+        NONE(0), IRIS(4), FINGERPRINT(6), FACE(7), MULTIPLE(8);
     }
     
     private fun generateKeyPair(keyName: String): KeyPair {
@@ -396,25 +458,8 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
 
         coroutineScope.launch {
             try {
-                val scope = scopeId(args.domain, args.name)
-                val alias = keystoreAlias(args.domain, args.name)
-                val dataKey = stringPreferencesKey(scope)
-                val ivKey = stringPreferencesKey("${scope}_iv")
-                val aesKey = stringPreferencesKey("${scope}_key")
-
-                // A record is only complete if ciphertext, IV, wrapped AES
-                // key, and the matching Keystore entry are all present.
-                // Otherwise hasData() would lie and getData() would fail.
-                val hasData = dataStore.data
-                    .map { preferences ->
-                        preferences.contains(dataKey) &&
-                            preferences.contains(ivKey) &&
-                            preferences.contains(aesKey)
-                    }
-                    .first() && getKeyPair(alias) != null
-
                 val result = JSObject()
-                result.put("hasData", hasData)
+                result.put("hasData", recordExists(args.domain, args.name))
                 invoke.resolve(result)
             } catch (e: Exception) {
                 invoke.reject("Failed to check data: ${e.message}")
@@ -433,58 +478,7 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
 
         coroutineScope.launch {
             try {
-                val scope = scopeId(args.domain, args.name)
-                val alias = keystoreAlias(args.domain, args.name)
-                val dataKey = stringPreferencesKey(scope)
-                val ivKey = stringPreferencesKey("${scope}_iv")
-                val aesKey = stringPreferencesKey("${scope}_key")
-
-                // Clear existing data
-                dataStore.edit { preferences ->
-                    preferences.remove(dataKey)
-                    preferences.remove(ivKey)
-                    preferences.remove(aesKey)
-                }
-
-                // Delete the key from keystore
-                val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
-                keyStore.load(null)
-                keyStore.deleteEntry(alias)
-
-                // Generate RSA key pair for encrypting AES key
-                val keyPair = generateKeyPair(alias)
-                
-                // Generate AES key for data encryption
-                val keyGenerator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES)
-                keyGenerator.init(AES_KEY_SIZE)
-                val secretKey = keyGenerator.generateKey()
-                
-                // Generate IV for AES-GCM
-                val iv = ByteArray(GCM_IV_LENGTH)
-                SecureRandom().nextBytes(iv)
-                
-                // Encrypt data with AES-GCM. AAD binds the ciphertext to its
-                // logical record (version, algorithm, domain, name) so a blob
-                // can't be replayed under a different identity.
-                val aesCipher = Cipher.getInstance(AES_CIPHER_CONFIG)
-                val gcmSpec = GCMParameterSpec(GCM_TAG_LENGTH, iv)
-                aesCipher.init(Cipher.ENCRYPT_MODE, secretKey, gcmSpec)
-                aesCipher.updateAAD(recordAad(args.domain, args.name))
-                val encryptedData = aesCipher.doFinal(args.data.toByteArray())
-                
-                // Encrypt AES key with RSA-OAEP (SHA-256 for both OAEP digest
-                // and MGF1; AndroidKeyStore otherwise defaults MGF1 to SHA-1).
-                val rsaCipher = Cipher.getInstance(RSA_CIPHER_CONFIG)
-                rsaCipher.init(Cipher.ENCRYPT_MODE, keyPair.getPublic(), oaepSpec())
-                val encryptedAesKey = rsaCipher.doFinal(secretKey.encoded)
-                
-                // Store encrypted data, IV, and encrypted AES key
-                dataStore.edit { preferences ->
-                    preferences[dataKey] = Base64.encodeToString(encryptedData, Base64.DEFAULT)
-                    preferences[ivKey] = Base64.encodeToString(iv, Base64.DEFAULT)
-                    preferences[aesKey] = Base64.encodeToString(encryptedAesKey, Base64.DEFAULT)
-                }
-                
+                writeRecord(args.domain, args.name, args.data.toByteArray())
                 invoke.resolve()
             } catch (e: Exception) {
                 invoke.reject("Failed to set data: ${e.message}")
@@ -502,7 +496,6 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
         }
 
         try {
-            val scope = scopeId(args.domain, args.name)
             val alias = keystoreAlias(args.domain, args.name)
             val keyPair = getKeyPair(alias)
             if (keyPair == null) {
@@ -535,37 +528,8 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
                             try {
                                 val rsaCipher = result.cryptoObject?.cipher
                                     ?: throw Exception("Cipher is null")
-                                
-                                val dataKey = stringPreferencesKey(scope)
-                                val ivKey = stringPreferencesKey("${scope}_iv")
-                                val aesKeyKey = stringPreferencesKey("${scope}_key")
-                                
-                                val preferences = dataStore.data.first()
-                                val encryptedData = preferences[dataKey]
-                                    ?: throw Exception("No data found")
-                                val ivString = preferences[ivKey]
-                                    ?: throw Exception("No IV found")
-                                val encryptedAesKey = preferences[aesKeyKey]
-                                    ?: throw Exception("No AES key found")
-                                
-                                // Decrypt AES key with RSA
-                                val aesKeyBytes = rsaCipher.doFinal(
-                                    Base64.decode(encryptedAesKey, Base64.DEFAULT)
-                                )
-                                val secretKey = SecretKeySpec(aesKeyBytes, "AES")
-                                
-                                // Decrypt data with AES-GCM; AAD must match the
-                                // identity bound at encrypt time or the tag fails.
-                                val iv = Base64.decode(ivString, Base64.DEFAULT)
-                                val aesCipher = Cipher.getInstance(AES_CIPHER_CONFIG)
-                                val gcmSpec = GCMParameterSpec(GCM_TAG_LENGTH, iv)
-                                aesCipher.init(Cipher.DECRYPT_MODE, secretKey, gcmSpec)
-                                aesCipher.updateAAD(recordAad(args.domain, args.name))
-
-                                val decryptedBytes = aesCipher.doFinal(
-                                    Base64.decode(encryptedData, Base64.DEFAULT)
-                                )
-                                
+                                val decryptedBytes = readRecord(rsaCipher, args.domain, args.name)
+
                                 val resultObject = JSObject()
                                 resultObject.put("domain", args.domain)
                                 resultObject.put("name", args.name)
@@ -613,30 +577,379 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
 
         coroutineScope.launch {
             try {
-                val scope = scopeId(args.domain, args.name)
-                val alias = keystoreAlias(args.domain, args.name)
-                val dataKey = stringPreferencesKey(scope)
-                val ivKey = stringPreferencesKey("${scope}_iv")
-                val aesKey = stringPreferencesKey("${scope}_key")
-
-                dataStore.edit { preferences ->
-                    preferences.remove(dataKey)
-                    preferences.remove(ivKey)
-                    preferences.remove(aesKey)
+                deleteRecord(args.domain, args.name)
+                invoke.resolve()
+            } catch (e: Exception) {
+                invoke.reject("Failed to remove data: ${e.message}")
+            }
+        }
+    }
+
+    // The AAD and the RSA key both belong to the record's (domain, name), so
+    // a rename decrypts the payload behind a prompt and re-encrypts it for
+    // the target. The target is written before the source is deleted.
+    @Command
+    fun renameData(invoke: Invoke) {
+        val args = invoke.parseArgs(RenameDataOptions::class.java)
+
+        (validateIdentity(args.domain, args.name)
+            ?: validateIdentity(args.newDomain, args.newName))?.let {
+            invoke.reject(it, "invalidInput")
+            return
+        }
+
+        try {
+            val (targetExists, sourceExists) = runBlocking {
+                recordExists(args.newDomain, args.newName) to recordExists(args.domain, args.name)
+            }
+            if (targetExists) {
+                invoke.reject("Target item already exists", "conflict")
+                return
+            }
+            val keyPair = getKeyPair(keystoreAlias(args.domain, args.name))
+            if (!sourceExists || keyPair == null) {
+                invoke.reject("Item not found", "itemNotFound")
+                return
+            }
+
+            val rsaCipher = Cipher.getInstance(RSA_CIPHER_CONFIG)
+            rsaCipher.init(Cipher.DECRYPT_MODE, keyPair.getPrivate(), oaepSpec())
+
+            val promptInfo = BiometricPrompt.PromptInfo.Builder()
+                .setTitle(biometryNameMap[biometryTypes[0]] ?: "")
+                .setDescription(args.reason)
+                .setNegativeButtonText(args.cancelTitle ?: "cancelTitle")
+                .build()
+
+            val biometricPrompt = BiometricPrompt(
+                activity as FragmentActivity,
+                ContextCompat.getMainExecutor(activity),
+                object : BiometricPrompt.AuthenticationCallback() {
+                    override fun onAuthenticationSucceeded(
+                        result: BiometricPrompt.AuthenticationResult
+                    ) {
+                        super.onAuthenticationSucceeded(result)
+
+                        coroutineScope.launch {
+                            val data = try {
+                                val cipher = result.cryptoObject?.cipher
+                                    ?: throw Exception("Cipher is null")
+                                readRecord(cipher, args.domain, args.name)
+                            } catch (e: Exception) {
+                                invoke.reject("Failed to decrypt data: ${e.message}", "decryptionFailed")
+                                return@launch
+                            }
+                            // If the target write fails, whatever part of the
+                            // target was written is deleted and the source is
+                            // untouched. If the source delete fails, the
+                            // target is a complete copy and is kept: the
+                            // source's preferences may already be gone, so
+                            // deleting the target could lose the item.
+                            try {
+                                writeRecord(args.newDomain, args.newName, data)
+                            } catch (e: Exception) {
+                                runCatching { deleteRecord(args.newDomain, args.newName) }
+                                invoke.reject("Failed to rename data: ${e.message}")
+                                return@launch
+                            } finally {
+                                data.fill(0)
+                            }
+                            try {
+                                deleteRecord(args.domain, args.name)
+                                invoke.resolve()
+                            } catch (e: Exception) {
+                                invoke.reject("Renamed, but failed to remove the source: ${e.message}")
+                            }
+                        }
+                    }
+
+                    override fun onAuthenticationError(
+                        errorCode: Int,
+                        errString: CharSequence
+                    ) {
+                        super.onAuthenticationError(errorCode, errString)
+                        invoke.reject(errString.toString(), biometryErrorCodeMap[errorCode])
+                    }
+
+                    override fun onAuthenticationFailed() {
+                        super.onAuthenticationFailed()
+                        // Don't reject here, let the user retry
+                    }
                 }
+            )
 
-                // Delete the key from keystore
-                val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
-                keyStore.load(null)
-                keyStore.deleteEntry(alias)
+            biometricPrompt.authenticate(promptInfo, BiometricPrompt.CryptoObject(rsaCipher))
+        } catch (e: Exception) {
+            invoke.reject("Failed to rename data: ${e.message}")
+        }
+    }
 
-                invoke.resolve()
+    // P-256 signing keys live in the Keystore, in StrongBox where the device
+    // has one. The Rust side falls back to a sealed key on "notSupported".
+    @Command
+    fun createKey(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyOptions::class.java)
+        validateIdentity(args.domain, args.name)?.let {
+            invoke.reject(it, "invalidInput")
+            return
+        }
+
+        val alias = signingKeyAlias(args.domain, args.name)
+        try {
+            deleteKeyEntry(alias)
+            val keyPair = try {
+                generateSigningKey(alias, strongBox = true)
             } catch (e: Exception) {
-                invoke.reject("Failed to remove data: ${e.message}")
+                generateSigningKey(alias, strongBox = false)
             }
+            val point = (keyPair.public as ECPublicKey).w
+            val publicKey = byteArrayOf(0x04) +
+                fixedLength(point.affineX) + fixedLength(point.affineY)
+            val ret = JSObject()
+            ret.put("publicKey", Base64.encodeToString(publicKey, Base64.NO_WRAP))
+            invoke.resolve(ret)
+        } catch (e: Exception) {
+            runCatching { deleteKeyEntry(alias) }
+            invoke.reject("Failed to create key: ${e.message}", "notSupported")
         }
     }
 
+    // Returns an X9.62 DER signature over SHA-256 of `data`.
+    @Command
+    fun signWithKey(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyUseOptions::class.java)
+        validateIdentity(args.domain, args.name)?.let {
+            invoke.reject(it, "invalidInput")
+            return
+        }
+
+        try {
+            val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
+            keyStore.load(null)
+            val privateKey = keyStore.getKey(signingKeyAlias(args.domain, args.name), null)
+                as PrivateKey?
+            if (privateKey == null) {
+                invoke.reject("No key pair found", "itemNotFound")
+                return
+            }
+            val payload = Base64.decode(args.data, Base64.DEFAULT)
+            val signature = Signature.getInstance("SHA256withECDSA")
+            signature.initSign(privateKey)
+
+            val promptInfo = BiometricPrompt.PromptInfo.Builder()
+                .setTitle(biometryNameMap[biometryTypes[0]] ?: "")
+                .setDescription(args.reason)
+                .setNegativeButtonText(args.cancelTitle ?: "cancelTitle")
+                .build()
+
+            val biometricPrompt = BiometricPrompt(
+                activity as FragmentActivity,
+                ContextCompat.getMainExecutor(activity),
+                object : BiometricPrompt.AuthenticationCallback() {
+                    override fun onAuthenticationSucceeded(
+                        result: BiometricPrompt.AuthenticationResult
+                    ) {
+                        super.onAuthenticationSucceeded(result)
+                        try {
+                            val unlocked = result.cryptoObject?.signature
+                                ?: throw Exception("Signature is null")
+                            unlocked.update(payload)
+                            val ret = JSObject()
+                            ret.put(
+                                "signature",
+                                Base64.encodeToString(unlocked.sign(), Base64.NO_WRAP)
+                            )
+                            invoke.resolve(ret)
+                        } catch (e: Exception) {
+                            invoke.reject("Failed to sign: ${e.message}")
+                        }
+                    }
+
+                    override fun onAuthenticationError(
+                        errorCode: Int,
+                        errString: CharSequence
+                    ) {
+                        super.onAuthenticationError(errorCode, errString)
+                        invoke.reject(errString.toString(), biometryErrorCodeMap[errorCode])
+                    }
+
+                    override fun onAuthenticationFailed() {
+                        super.onAuthenticationFailed()
+                        // Don't reject here, let the user retry
+                    }
+                }
+            )
+
+            biometricPrompt.authenticate(promptInfo, BiometricPrompt.CryptoObject(signature))
+        } catch (e: Exception) {
+            invoke.reject("Failed to sign: ${e.message}")
+        }
+    }
+
+    @Command
+    fun deleteKey(invoke: Invoke) {
+        val args = invoke.parseArgs(KeyOptions::class.java)
+        validateIdentity(args.domain, args.name)?.let {
+            invoke.reject(it, "invalidInput")
+            return
+        }
+
+        try {
+            deleteKeyEntry(signingKeyAlias(args.domain, args.name))
+            invoke.resolve()
+        } catch (e: Exception) {
+            invoke.reject("Failed to delete key: ${e.message}")
+        }
+    }
+
+    private fun generateSigningKey(alias: String, strongBox: Boolean): KeyPair {
+        val keyPairGenerator = KeyPairGenerator.getInstance(
+            KeyProperties.KEY_ALGORITHM_EC,
+            ANDROID_KEYSTORE
+        )
+
+        val builder = KeyGenParameterSpec.Builder(alias, KeyProperties.PURPOSE_SIGN)
+            .setAlgorithmParameterSpec(ECGenParameterSpec("secp256r1"))
+            .setDigests(KeyProperties.DIGEST_SHA256)
+            .setUserAuthenticationRequired(true)
+            .setInvalidatedByBiometricEnrollment(true)
+        if (strongBox) {
+            if (Build.VERSION.SDK_INT < Build.VERSION_CODES.P) {
+                throw Exception("StrongBox requires API 28")
+            }
+            builder.setIsStrongBoxBacked(true)
+        }
+
+        keyPairGenerator.initialize(builder.build())
+        return keyPairGenerator.generateKeyPair()
+    }
+
+    private fun deleteKeyEntry(alias: String) {
+        val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
+        keyStore.load(null)
+        keyStore.deleteEntry(alias)
+    }
+
+    // Left-pads (or strips the sign byte of) a coordinate to 32 bytes.
+    private fun fixedLength(value: BigInteger): ByteArray {
+        val bytes = value.toByteArray()
+        return when {
+            bytes.size == EC_COORDINATE_LEN -> bytes
+            bytes.size > EC_COORDINATE_LEN -> bytes.copyOfRange(bytes.size - EC_COORDINATE_LEN, bytes.size)
+            else -> ByteArray(EC_COORDINATE_LEN - bytes.size) + bytes
+        }
+    }
+
+    // A record is only complete if ciphertext, IV, wrapped AES key, and the
+    // matching Keystore entry are all present. Otherwise hasData() would lie
+    // and getData() would fail.
+    private suspend fun recordExists(domain: String, name: String): Boolean {
+        val scope = scopeId(domain, name)
+        val dataKey = stringPreferencesKey(scope)
+        val ivKey = stringPreferencesKey("${scope}_iv")
+        val aesKey = stringPreferencesKey("${scope}_key")
+
+        return dataStore.data
+            .map { preferences ->
+                preferences.contains(dataKey) &&
+                    preferences.contains(ivKey) &&
+                    preferences.contains(aesKey)
+            }
+            .first() && getKeyPair(keystoreAlias(domain, name)) != null
+    }
+
+    // Replaces the record with a fresh RSA key pair, AES key and IV.
+    private suspend fun writeRecord(domain: String, name: String, data: ByteArray) {
+        val scope = scopeId(domain, name)
+        val dataKey = stringPreferencesKey(scope)
+        val ivKey = stringPreferencesKey("${scope}_iv")
+        val aesKey = stringPreferencesKey("${scope}_key")
+
+        deleteRecord(domain, name)
+
+        // Generate RSA key pair for encrypting AES key
+        val keyPair = generateKeyPair(keystoreAlias(domain, name))
+
+        // Generate AES key for data encryption
+        val keyGenerator = KeyGenerator.getInstance(KeyProperties.KEY_ALGORITHM_AES)
+        keyGenerator.init(AES_KEY_SIZE)
+        val secretKey = keyGenerator.generateKey()
+
+        // Generate IV for AES-GCM
+        val iv = ByteArray(GCM_IV_LENGTH)
+        SecureRandom().nextBytes(iv)
+
+        // Encrypt data with AES-GCM. AAD binds the ciphertext to its
+        // logical record (version, algorithm, domain, name) so a blob
+        // can't be replayed under a different identity.
+        val aesCipher = Cipher.getInstance(AES_CIPHER_CONFIG)
+        val gcmSpec = GCMParameterSpec(GCM_TAG_LENGTH, iv)
+        aesCipher.init(Cipher.ENCRYPT_MODE, secretKey, gcmSpec)
+        aesCipher.updateAAD(recordAad(domain, name))
+        val encryptedData = aesCipher.doFinal(data)
+
+        // Encrypt AES key with RSA-OAEP (SHA-256 for both OAEP digest
+        // and MGF1; AndroidKeyStore otherwise defaults MGF1 to SHA-1).
+        val rsaCipher = Cipher.getInstance(RSA_CIPHER_CONFIG)
+        rsaCipher.init(Cipher.ENCRYPT_MODE, keyPair.getPublic(), oaepSpec())
+        val encryptedAesKey = rsaCipher.doFinal(secretKey.encoded)
+
+        // Store encrypted data, IV, and encrypted AES key
+        dataStore.edit { preferences ->
+            preferences[dataKey] = Base64.encodeToString(encryptedData, Base64.DEFAULT)
+            preferences[ivKey] = Base64.encodeToString(iv, Base64.DEFAULT)
+            preferences[aesKey] = Base64.encodeToString(encryptedAesKey, Base64.DEFAULT)
+        }
+    }
+
+    // Decrypts the record with an RSA cipher unlocked by a biometric prompt.
+    private suspend fun readRecord(rsaCipher: Cipher, domain: String, name: String): ByteArray {
+        val scope = scopeId(domain, name)
+        val dataKey = stringPreferencesKey(scope)
+        val ivKey = stringPreferencesKey("${scope}_iv")
+        val aesKeyKey = stringPreferencesKey("${scope}_key")
+
+        val preferences = dataStore.data.first()
+        val encryptedData = preferences[dataKey]
+            ?: throw Exception("No data found")
+        val ivString = preferences[ivKey]
+            ?: throw Exception("No IV found")
+        val encryptedAesKey = preferences[aesKeyKey]
+            ?: throw Exception("No AES key found")
+
+        // Decrypt AES key with RSA
+        val aesKeyBytes = rsaCipher.doFinal(
+            Base64.decode(encryptedAesKey, Base64.DEFAULT)
+        )
+        val secretKey = SecretKeySpec(aesKeyBytes, "AES")
+
+        // Decrypt data with AES-GCM; AAD must match the
+        // identity bound at encrypt time or the tag fails.
+        val iv = Base64.decode(ivString, Base64.DEFAULT)
+        val aesCipher = Cipher.getInstance(AES_CIPHER_CONFIG)
+        val gcmSpec = GCMParameterSpec(GCM_TAG_LENGTH, iv)
+        aesCipher.init(Cipher.DECRYPT_MODE, secretKey, gcmSpec)
+        aesCipher.updateAAD(recordAad(domain, name))
+
+        return aesCipher.doFinal(
+            Base64.decode(encryptedData, Base64.DEFAULT)
+        )
+    }
+
+    private suspend fun deleteRecord(domain: String, name: String) {
+        val scope = scopeId(domain, name)
+        dataStore.edit { preferences ->
+            preferences.remove(stringPreferencesKey(scope))
+            preferences.remove(stringPreferencesKey("${scope}_iv"))
+            preferences.remove(stringPreferencesKey("${scope}_key"))
+        }
+
+        // Delete the key from keystore
+        val keyStore = KeyStore.getInstance(ANDROID_KEYSTORE)
+        keyStore.load(null)
+        keyStore.deleteEntry(keystoreAlias(domain, name))
+    }
+
     // Scope every storage identifier by both domain and name so records under
     // the same name in different domains — or different names in the same
     // domain — never collide or invalidate each other.
@@ -666,13 +979,23 @@ class BiometryPlugin(private val activity: Activity): Plugin(activity) {
     // Keystore aliases vary in length tolerance across OEMs; hash the scoped
     // identity to a fixed-length, safe-character alias.
     private fun keystoreAlias(domain: String, name: String): String {
+        return "$KEYSTORE_ALIAS_PREFIX${scopeDigest(domain, name)}"
+    }
+
+    // Signing keys get their own prefix so they never collide with the RSA
+    // key of a stored item with the same domain and name.
+    private fun signingKeyAlias(domain: String, name: String): String {
+        return "$SIGNING_KEY_ALIAS_PREFIX${scopeDigest(domain, name)}"
+    }
+
+    private fun scopeDigest(domain: String, name: String): String {
         val digest = MessageDigest.getInstance("SHA-256")
             .digest(scopeId(domain, name).toByteArray(Charsets.UTF_8))
         val hex = StringBuilder(digest.size * 2)
         for (b in digest) {
             hex.append(String.format("%02x", b))
         }
-        return "$KEYSTORE_ALIAS_PREFIX$hex"
+        return hex.toString()
     }
 
     private fun oaepSpec(): OAEPParameterSpec {
@@ -5,6 +5,25 @@ const COMMANDS: &[&str] = &[
     "get_data",
     "set_data",
     "remove_data",
+    "rename_data",
+    "get_metadata",
+    "purge_expired",
+    "export_domain",
+    "import_domain",
+    "fetch_with_secret",
+    "create_key",
+    "sign",
+    "encrypt",
+    "decrypt",
+    "encrypt_file",
+    "decrypt_file",
+    "derive_key",
+    "import_otp",
+    "generate_otp",
+    "delete_otp",
+    "public_key",
+    "credential_public_key",
+    "delete_key",
 ];
 
 fn main() {
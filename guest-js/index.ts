@@ -7,14 +7,52 @@ import { invoke } from "@tauri-apps/api/core";
 export enum BiometryType {
   /** No biometry available */
   None = 0,
-  /** Automatic biometry (e.g., Face ID, Touch ID) */
+  /** Windows Hello with no biometric sensor found (e.g. PIN only) */
   Auto = 1,
-  /** Apple Touch ID or Android fingerprint authentication */
+  /** Apple Touch ID */
   TouchID = 2,
-  /** Apple Face ID or Android face authentication */
+  /** Apple Face ID */
   FaceID = 3,
-  /** Android iris authentication (Samsung devices) */
+  /** Iris authentication (Android, Windows) */
   Iris = 4,
+  /** Apple Optic ID */
+  OpticID = 5,
+  /** Fingerprint reader (Android, Windows) */
+  Fingerprint = 6,
+  /** Face recognition (Android, Windows) */
+  Face = 7,
+  /** More than one modality; see `Status.modalities` */
+  Multiple = 8,
+}
+
+/**
+ * How strongly the available biometry identifies the user.
+ * - `strong`: can unlock hardware-backed keys (Android Class 3, Touch ID, Face ID)
+ * - `weak`: Android Class 2, enough for `authenticate` but not for storage
+ * - `unknown`: available, but the platform doesn't report a class (Windows Hello)
+ * - `none`: no biometry available
+ */
+export type BiometryStrength = "strong" | "weak" | "unknown" | "none";
+
+/**
+ * What the current machine can do, so a UI can hide features that would
+ * only fail.
+ */
+export interface Capabilities {
+  /** `setData` / `getData` and the other storage commands work */
+  storage: boolean;
+  /** A device passcode, PIN or password can stand in for biometry (`allowDeviceCredential`) */
+  deviceCredential: boolean;
+  /** The key, `deriveKey` and OTP commands work */
+  keys: boolean;
+  strength: BiometryStrength;
+  /** `keychain` (macOS, iOS), `windowsHello`, `androidKeystore` or `none` (Linux) */
+  backend: string;
+  /**
+   * The OS version on macOS and iOS, the API level on Android, the
+   * `webauthn.dll` API version on Windows
+   */
+  backendVersion: string | null;
 }
 
 /**
@@ -28,18 +66,102 @@ export interface Status {
   /** Error message if biometry is not available */
   error?: string;
   /** Specific error code for debugging purposes */
-  errorCode?:
-    | "appCancel"
-    | "authenticationFailed"
-    | "invalidContext"
-    | "notInteractive"
-    | "passcodeNotSet"
-    | "systemCancel"
-    | "userCancel"
-    | "userFallback"
-    | "biometryLockout"
-    | "biometryNotAvailable"
-    | "biometryNotEnrolled";
+  errorCode?: ErrorCode;
+  /**
+   * Every biometric modality the device offers, empty when biometry is
+   * unavailable
+   */
+  modalities: BiometryType[];
+  /** What this machine supports */
+  capabilities: Capabilities;
+}
+
+/**
+ * Every error code the plugin reports. Mirrors the Rust `ErrorCode` enum.
+ */
+export type ErrorCode =
+  | "userCancel"
+  | "authenticationFailed"
+  | "biometryNotAvailable"
+  | "biometryNotEnrolled"
+  | "biometryLockout"
+  | "systemCancel"
+  | "appCancel"
+  | "invalidContext"
+  | "notInteractive"
+  | "passcodeNotSet"
+  | "userFallback"
+  | "noDeviceCredential"
+  | "authenticationRequired"
+  | "itemNotFound"
+  | "conflict"
+  | "dataNeedsReenrollment"
+  | "credentialCreationFailed"
+  | "encryptionFailed"
+  | "decryptionFailed"
+  | "keychainError"
+  | "rustOnly"
+  | "invalidHandle"
+  | "invalidInput"
+  | "scopeDenied"
+  | "notSupported"
+  | "unsupportedPlatform"
+  | "httpError"
+  | "timeout"
+  | "ioError"
+  | "internalError"
+  | "unknown";
+
+/**
+ * The shape of every error the plugin's commands reject with.
+ *
+ * @example
+ * ```typescript
+ * try {
+ *   await getData({ domain: 'com.myapp', name: 'token', reason: 'Sign in' });
+ * } catch (e) {
+ *   const error = e as BiometryError;
+ *   if (error.code === 'userCancel') return;
+ *   throw error;
+ * }
+ * ```
+ */
+export interface BiometryError {
+  /** Machine-readable error code */
+  code: ErrorCode;
+  /** Human-readable description */
+  message: string;
+  /**
+   * Error-specific details, or `null`. For `scopeDenied` this is a
+   * {@link ScopeDeniedData} or, for file paths, `{ path }`.
+   */
+  data: unknown;
+}
+
+/**
+ * `data` of a `scopeDenied` error for a `(domain, name)` pair.
+ */
+export interface ScopeDeniedData {
+  domain: string;
+  name: string;
+  /** The request URL, when `fetchWithSecret` was refused for it */
+  url: string | null;
+  /**
+   * `denied` if a `deny` entry matched, `notAllowed` if no `allow` entry
+   * did (usually a missing capability entry).
+   */
+  reason: "denied" | "notAllowed";
+}
+
+/**
+ * Options for {@link checkStatus}.
+ */
+export interface StatusOptions {
+  /**
+   * Probe the platform even if a cached status is available.
+   * @default false
+   */
+  fresh?: boolean;
 }
 
 /**
@@ -114,8 +236,11 @@ export interface DataResponse {
   /** The name/key of the retrieved data */
   name: string;
 
-  /** The actual data content as a string */
-  data: string;
+  /** The actual data content as a string. Absent when `handle` is set */
+  data?: string;
+
+  /** The opaque handle issued in place of `data`, if one was requested */
+  handle?: string;
 }
 
 /**
@@ -144,6 +269,29 @@ export interface GetDataOptions {
    * @default "Cancel"
    */
   cancelTitle?: string;
+
+  /**
+   * Return an opaque handle instead of the data. Only Rust code can turn it
+   * back into the secret, via `app.biometry().resolve_handle(..)`.
+   */
+  handle?: HandleOptions;
+}
+
+/**
+ * Options for the handle returned by `getData`.
+ */
+export interface HandleOptions {
+  /**
+   * Lifetime in milliseconds, at most 300000.
+   * @default 60000
+   */
+  ttl?: number;
+
+  /**
+   * Whether the first resolve consumes the handle.
+   * @default true
+   */
+  singleUse?: boolean;
 }
 
 /**
@@ -162,26 +310,641 @@ export interface SetDataOptions {
   name: string;
 
   /**
-   * The data to store as a string.
-   * For complex data, use JSON.stringify().
+   * The data to store as a string.
+   * For complex data, use JSON.stringify().
+   */
+  data: string;
+
+  /**
+   * Optional human-readable label, returned by `getMetadata`.
+   * Replaces the previous label on every write. Not encrypted.
+   */
+  label?: string;
+
+  /**
+   * Optional custom string attributes (at most 32), returned by `getMetadata`.
+   * Replaces the previous attributes on every write. Not encrypted.
+   */
+  attributes?: Record<string, string>;
+
+  /**
+   * Absolute expiry, in milliseconds since the Unix epoch (e.g. `Date.now() + 3600_000`).
+   * Once passed, the item is treated as absent and deleted on next access.
+   * Mutually exclusive with `ttl`.
+   */
+  expiresAt?: number;
+
+  /**
+   * Lifetime in milliseconds, counted from this write.
+   * Mutually exclusive with `expiresAt`.
+   */
+  ttl?: number;
+
+  /**
+   * Only write if no item exists yet under this domain and name.
+   * Fails with the `conflict` error code otherwise.
+   * @default false
+   */
+  ifAbsent?: boolean;
+
+  /**
+   * Only write if the item's current `revision` (see `getMetadata`) equals this value.
+   * Fails with the `conflict` error code otherwise.
+   */
+  ifVersion?: number;
+
+  /**
+   * Keep the item away from the webview: `getData` rejects it with the
+   * `rustOnly` error code, as does `fetchWithSecret` unless the scope opts
+   * in, and `exportDomain` skips it, while
+   * `app.biometry().get_data(..)` in Rust still returns it.
+   * @default false
+   */
+  rustOnly?: boolean;
+}
+
+/**
+ * Non-secret metadata kept for every stored item.
+ */
+export interface ItemMetadata {
+  /** The domain/namespace of the item */
+  domain: string;
+
+  /** The name/key of the item */
+  name: string;
+
+  /** Time of the first write, in milliseconds since the Unix epoch */
+  createdAt: number;
+
+  /** Time of the latest write, in milliseconds since the Unix epoch */
+  updatedAt: number;
+
+  /** Incremented on every write, starting at 1. Use with `ifVersion` */
+  revision: number;
+
+  /** The label passed to the latest `setData`, if any */
+  label?: string;
+
+  /** The attributes passed to the latest `setData` */
+  attributes: Record<string, string>;
+
+  /** Expiry time, in milliseconds since the Unix epoch, if the item has one */
+  expiresAt?: number;
+
+  /** Whether the item was written with `rustOnly` */
+  rustOnly: boolean;
+}
+
+/**
+ * Options for purging expired items.
+ */
+export interface PurgeExpiredOptions {
+  /**
+   * Restricts the purge to one domain.
+   * Omit to purge every domain the capability scope allows.
+   */
+  domain?: string;
+}
+
+/**
+ * Options for removing secure data.
+ * Same as DataOptions - requires domain and name to identify the data.
+ */
+export type RemoveDataOptions = DataOptions;
+
+/**
+ * Options for renaming secure data.
+ */
+export interface RenameDataOptions {
+  /**
+   * The current domain of the item.
+   */
+  domain: string;
+  /**
+   * The current name of the item.
+   */
+  name: string;
+  /**
+   * The domain to move the item to. May equal `domain`.
+   */
+  newDomain: string;
+  /**
+   * The name to move the item to.
+   */
+  newName: string;
+  /**
+   * Shown in the authentication prompt on Android, where the item has to be
+   * decrypted to move it.
+   */
+  reason?: string;
+  /**
+   * Cancel button text for the Android prompt.
+   */
+  cancelTitle?: string;
+}
+
+/**
+ * Options for exporting a domain to an encrypted archive.
+ */
+export interface ExportDomainOptions {
+  /**
+   * The domain to export.
+   */
+  domain: string;
+  /**
+   * Passphrase the archive is encrypted under. Needed again to import it.
+   */
+  passphrase: string;
+  /**
+   * The reason shown in the authentication prompt for each item read.
+   */
+  reason: string;
+  /**
+   * Custom text for the cancel button in the authentication prompt.
+   */
+  cancelTitle?: string;
+  /**
+   * The names of the items to export. Needed for items the metadata index
+   * doesn't know about, such as ones stored before metadata was tracked.
+   * Omit to export every indexed item the scope allows.
+   */
+  names?: string[];
+}
+
+/**
+ * Result of exporting a domain.
+ */
+export interface ExportDomainResponse {
+  /**
+   * The archive text. Save it wherever the app keeps backups.
+   */
+  archive: string;
+  /**
+   * Items left out because they are `rustOnly` or no longer exist.
+   */
+  skipped: DataOptions[];
+  /**
+   * Exported items that had no metadata. Only items listed in `names` can
+   * show up here; without `names`, such items are not found at all.
+   */
+  unindexed: DataOptions[];
+}
+
+/**
+ * Options for importing an encrypted archive.
+ */
+export interface ImportDomainOptions {
+  /**
+   * Archive text returned by `exportDomain`.
+   */
+  archive: string;
+  /**
+   * The passphrase the archive was exported with.
+   */
+  passphrase: string;
+  /**
+   * Replace items that already exist locally.
+   * By default they are skipped.
+   * @default false
+   */
+  overwrite?: boolean;
+}
+
+/**
+ * Result of importing an archive.
+ */
+export interface ImportDomainResponse {
+  /**
+   * Items written to local storage.
+   */
+  imported: DataOptions[];
+  /**
+   * Items left alone because they already exist locally or have expired.
+   */
+  skipped: DataOptions[];
+}
+
+/**
+ * Options for an HTTP request that carries a stored item in one header.
+ */
+export interface FetchWithSecretOptions {
+  /**
+   * The domain/namespace of the item to send.
+   */
+  domain: string;
+
+  /**
+   * The name/key of the item to send.
+   */
+  name: string;
+
+  /**
+   * The reason for requesting authentication, shown to the user.
+   */
+  reason: string;
+
+  /**
+   * Text for the cancel button in the authentication dialog.
+   * @default "Cancel"
+   */
+  cancelTitle?: string;
+
+  /**
+   * Absolute `http` or `https` URL. Must be listed in the `urls` of a
+   * matching scope entry.
+   */
+  url: string;
+
+  /**
+   * Request method.
+   * @default "GET"
+   */
+  method?: string;
+
+  /**
+   * Additional request headers, sent as-is.
+   */
+  headers?: Record<string, string>;
+
+  /**
+   * Header that carries the secret.
+   * @example "Authorization"
+   */
+  secretHeader: string;
+
+  /**
+   * Value of `secretHeader`, with `{secret}` replaced by the item's data.
+   * @example "Bearer {secret}"
+   */
+  template: string;
+
+  /**
+   * Request body.
+   */
+  body?: string;
+
+  /**
+   * Timeout for the whole request, in milliseconds.
+   * @default 30000
+   */
+  timeout?: number;
+}
+
+/**
+ * Response to `fetchWithSecret`.
+ */
+export interface FetchResponse {
+  /** HTTP status code. Redirects are not followed */
+  status: number;
+
+  /** Response headers by lowercase name, repeated headers joined with ", " */
+  headers: Record<string, string>;
+
+  /** Response body, decoded as UTF-8 text. At most 8 MiB */
+  body: string;
+}
+
+/**
+ * Algorithm of a key created with `createKey`.
+ * - `P-256`: ECDSA signing over NIST P-256 with SHA-256.
+ * - `Ed25519`: EdDSA signing over Curve25519.
+ * - `ECDH-P-256`: encryption key for `encrypt` / `decrypt`. Cannot sign.
+ */
+export type KeyAlgorithm = "P-256" | "Ed25519" | "ECDH-P-256";
+
+/**
+ * Options for creating a signing key.
+ */
+export interface CreateKeyOptions {
+  /**
+   * The domain/namespace of the key.
+   */
+  domain: string;
+  /**
+   * The name of the key within the domain.
+   */
+  name: string;
+  /**
+   * The signature algorithm.
+   */
+  alg: KeyAlgorithm;
+}
+
+/**
+ * Options for signing with a stored key.
+ */
+export interface SignOptions {
+  /**
+   * The domain of the key.
+   */
+  domain: string;
+  /**
+   * The name of the key.
+   */
+  name: string;
+  /**
+   * The bytes to sign.
+   */
+  payload: Uint8Array | number[];
+  /**
+   * The reason shown in the authentication prompt.
+   */
+  reason: string;
+  /**
+   * Custom text for the cancel button in the authentication prompt.
+   */
+  cancelTitle?: string;
+}
+
+/**
+ * Options for encrypting with a stored key.
+ */
+export interface EncryptOptions {
+  /**
+   * The domain of the `ECDH-P-256` key.
+   */
+  domain: string;
+  /**
+   * The name of the `ECDH-P-256` key.
+   */
+  name: string;
+  /**
+   * The bytes to encrypt.
+   */
+  plaintext: Uint8Array | number[];
+}
+
+/**
+ * Options for decrypting with a stored key.
+ */
+export interface DecryptOptions {
+  /**
+   * The domain of the `ECDH-P-256` key.
+   */
+  domain: string;
+  /**
+   * The name of the `ECDH-P-256` key.
+   */
+  name: string;
+  /**
+   * The ciphertext returned by `encrypt`.
+   */
+  ciphertext: string;
+  /**
+   * The reason shown in the authentication prompt.
+   */
+  reason: string;
+  /**
+   * Custom text for the cancel button in the authentication prompt.
+   */
+  cancelTitle?: string;
+}
+
+export interface EncryptFileOptions {
+  /**
+   * The domain of the `ECDH-P-256` key.
+   */
+  domain: string;
+  /**
+   * The name of the `ECDH-P-256` key.
+   */
+  name: string;
+  /**
+   * Absolute path of the file to encrypt. Must be allowed by the fs plugin
+   * scope.
+   */
+  input: string;
+  /**
+   * Absolute path to write the encrypted file to. Must be allowed by the fs
+   * plugin scope. Replaced if it exists.
+   */
+  output: string;
+}
+
+export interface DecryptFileOptions {
+  /**
+   * The domain of the `ECDH-P-256` key.
+   */
+  domain: string;
+  /**
+   * The name of the `ECDH-P-256` key.
+   */
+  name: string;
+  /**
+   * Absolute path of a file written by `encryptFile`.
+   */
+  input: string;
+  /**
+   * Absolute path to write the plaintext to. Replaced if it exists.
+   */
+  output: string;
+  /**
+   * The reason shown in the authentication prompt.
+   */
+  reason: string;
+  /**
+   * Custom text for the cancel button in the authentication prompt.
+   */
+  cancelTitle?: string;
+}
+
+export interface DeriveKeyOptions {
+  /**
+   * The domain whose root secret the key is derived from.
+   */
+  domain: string;
+  /**
+   * Distinguishes keys derived in the same domain, e.g. `"database"`.
+   */
+  context: string;
+  /**
+   * The number of bytes to derive, from 1 to 8160.
+   */
+  length: number;
+  /**
+   * The reason shown in the authentication prompt.
+   */
+  reason: string;
+  /**
+   * Custom text for the cancel button in the authentication prompt.
+   */
+  cancelTitle?: string;
+}
+
+/**
+ * Options identifying a stored key.
+ */
+export type KeyOptions = DataOptions;
+
+/**
+ * Options for {@link credentialPublicKey}.
+ */
+export interface CredentialKeyOptions {
+  domain: string;
+}
+
+/**
+ * One-time password type: time-based (RFC 6238) or counter-based (RFC 4226).
+ */
+export type OtpKind = "totp" | "hotp";
+
+/**
+ * HMAC hash used to compute one-time passwords.
+ */
+export type OtpAlgorithm = "SHA1" | "SHA256" | "SHA512";
+
+export interface ImportOtpOptions {
+  /**
+   * The domain to store the OTP in.
+   */
+  domain: string;
+  /**
+   * The name to store the OTP under.
+   */
+  name: string;
+  /**
+   * An `otpauth://totp/...` or `otpauth://hotp/...` URI.
+   */
+  uri: string;
+  /**
+   * Replace an existing OTP with the same domain and name. Defaults to
+   * `false`, which rejects with `conflict`.
+   */
+  overwrite?: boolean;
+}
+
+export interface GenerateOtpOptions {
+  /**
+   * The domain of the OTP.
+   */
+  domain: string;
+  /**
+   * The name of the OTP.
+   */
+  name: string;
+  /**
+   * The reason shown in the authentication prompt.
+   */
+  reason: string;
+  /**
+   * Custom text for the cancel button in the authentication prompt.
+   */
+  cancelTitle?: string;
+}
+
+/**
+ * Options identifying an imported OTP.
+ */
+export type OtpOptions = DataOptions;
+
+/**
+ * The non-secret parameters of an imported OTP.
+ */
+export interface OtpInfo {
+  domain: string;
+  name: string;
+  kind: OtpKind;
+  algorithm: OtpAlgorithm;
+  digits: number;
+  /**
+   * TOTP time step in seconds.
+   */
+  period?: number;
+  /**
+   * HOTP counter the next code will use.
+   */
+  counter?: number;
+  issuer?: string;
+  account?: string;
+  /**
+   * Import time, in milliseconds since the Unix epoch.
+   */
+  createdAt: number;
+}
+
+export interface OtpCode {
+  code: string;
+  /**
+   * TOTP: when the code stops being current, in milliseconds since the Unix
+   * epoch.
+   */
+  expiresAt?: number;
+  /**
+   * HOTP: the counter value the code was generated for.
+   */
+  counter?: number;
+}
+
+/**
+ * Public half of a stored key.
+ */
+export interface PublicKeyResponse {
+  domain: string;
+  name: string;
+  alg: KeyAlgorithm;
+  /**
+   * Uncompressed SEC1 point (65 bytes) for P-256 and ECDH-P-256, raw 32-byte
+   * key for Ed25519.
+   */
+  publicKey: Uint8Array;
+  /**
+   * DER-encoded SubjectPublicKeyInfo.
+   */
+  spki: Uint8Array;
+  /**
+   * PEM (`-----BEGIN PUBLIC KEY-----`) of `spki`.
+   */
+  pem: string;
+  /**
+   * JSON Web Key. `kid` is the RFC 7638 thumbprint.
+   */
+  jwk: PublicJwk;
+  /**
+   * CBOR-encoded COSE_Key.
+   */
+  coseKey: Uint8Array;
+  /**
+   * RFC 7638 JWK thumbprint (base64url SHA-256).
+   * Stable for the life of the key, so it can serve as a device identifier.
+   */
+  thumbprint: string;
+  /**
+   * `true` when the private key lives in the Secure Enclave or the Android
+   * Keystore, `false` when it is sealed in the platform store.
    */
-  data: string;
+  platform: boolean;
 }
 
 /**
- * Options for removing secure data.
- * Same as DataOptions - requires domain and name to identify the data.
+ * Public key as a JSON Web Key.
  */
-export type RemoveDataOptions = DataOptions;
+export interface PublicJwk {
+  kty: "EC" | "OKP";
+  crv: "P-256" | "Ed25519";
+  x: string;
+  /**
+   * Only present for P-256 keys.
+   */
+  y?: string;
+  alg: "ES256" | "EdDSA" | "ECDH-ES";
+  kid: string;
+}
 
 /**
  * Checks the availability and type of biometric authentication on the device.
  *
+ * The result is cached for 30 seconds. The cache is dropped when a window
+ * gains focus or a prompting command fails; pass `{ fresh: true }` to
+ * probe the platform regardless.
+ *
+ * @param options - Pass `fresh: true` to bypass the cache
  * @returns {Promise<Status>} A promise that resolves to a Status object containing:
  * - `isAvailable`: Whether biometry is available and configured
- * - `biometryType`: The type of biometry available (None, TouchID, FaceID, or Iris)
+ * - `biometryType`: The type of biometry available, see {@link BiometryType}
+ * - `modalities`: Every modality the device offers
  * - `error`: Error message if biometry is not available
  * - `errorCode`: Specific error code for debugging
+ * - `capabilities`: What this machine supports
  *
  * @example
  * ```typescript
@@ -193,8 +956,10 @@ export type RemoveDataOptions = DataOptions;
  * }
  * ```
  */
-export async function checkStatus(): Promise<Status> {
-  return await invoke("plugin:biometry|status");
+export async function checkStatus(
+  options: StatusOptions = {},
+): Promise<Status> {
+  return await invoke("plugin:biometry|status", { options });
 }
 
 /**
@@ -347,3 +1112,535 @@ export async function setData(options: SetDataOptions): Promise<void> {
 export async function removeData(options: RemoveDataOptions): Promise<void> {
   await invoke("plugin:biometry|remove_data", { options });
 }
+
+/**
+ * Moves secure data to a new domain and/or name without exposing it to the webview.
+ * The item keeps its metadata; the rename counts as a write and bumps `revision`.
+ * Both the source and the target must be allowed by the capability scope.
+ *
+ * On macOS and iOS this is a single keychain update and does not prompt. On
+ * Windows the item is re-encrypted for its new name, which takes one Windows
+ * Hello prompt (two when moving across domains). On Android it is re-encrypted
+ * after one biometric prompt.
+ *
+ * @param {RenameDataOptions} options - The current and new domain and name.
+ *
+ * @returns {Promise<void>} A promise that resolves when the item has been moved.
+ *
+ * @throws {Error} With code `conflict` if an item already exists at the target.
+ *
+ * @example
+ * ```typescript
+ * await renameData({
+ *   domain: 'com.myapp',
+ *   name: 'api_token',
+ *   newDomain: 'com.myapp',
+ *   newName: 'api_token_v2'
+ * });
+ * ```
+ */
+export async function renameData(options: RenameDataOptions): Promise<void> {
+  await invoke("plugin:biometry|rename_data", { options });
+}
+
+/**
+ * Returns the non-secret metadata of a stored item.
+ * This method does not trigger biometric authentication.
+ *
+ * @param {DataOptions} options - The domain and name identifying the data.
+ *
+ * @returns {Promise<ItemMetadata | null>} A promise that resolves to the item's
+ *                                         metadata, or `null` if the item does not
+ *                                         exist or was stored before metadata was
+ *                                         tracked.
+ *
+ * @example
+ * ```typescript
+ * const meta = await getMetadata({
+ *   domain: 'com.myapp',
+ *   name: 'api_token'
+ * });
+ *
+ * if (meta) {
+ *   console.log('Last updated:', new Date(meta.updatedAt));
+ * }
+ * ```
+ */
+export async function getMetadata(
+  options: DataOptions,
+): Promise<ItemMetadata | null> {
+  return await invoke("plugin:biometry|get_metadata", { options });
+}
+
+/**
+ * Deletes every stored item whose `expiresAt` / `ttl` has passed.
+ * Expired items are also deleted lazily by `hasData` and `getData`; call this
+ * at startup so they don't linger if the app never touches them again.
+ * Items outside the capability scope are skipped.
+ * This method does not trigger biometric authentication.
+ *
+ * @param {PurgeExpiredOptions} options - Optional domain restriction.
+ *
+ * @returns {Promise<DataOptions[]>} A promise that resolves to the domain and name
+ *                                   of every purged item.
+ *
+ * @example
+ * ```typescript
+ * const purged = await purgeExpired({ domain: 'com.myapp' });
+ * console.log(`Purged ${purged.length} expired items`);
+ * ```
+ */
+export async function purgeExpired(
+  options: PurgeExpiredOptions = {},
+): Promise<DataOptions[]> {
+  return await invoke("plugin:biometry|purge_expired", { options });
+}
+
+/**
+ * Exports every item of a domain that the capability scope allows into an
+ * archive encrypted under a passphrase (Argon2id + AES-256-GCM).
+ * Each item is read with its own biometric authentication, so the user is
+ * prompted once per exported item. Items stored before metadata was
+ * tracked are only included when listed in `names`.
+ *
+ * @param {ExportDomainOptions} options - The domain, passphrase and prompt text.
+ *
+ * @returns {Promise<ExportDomainResponse>} A promise that resolves to the
+ *                                          archive text and the skipped and
+ *                                          unindexed items.
+ *
+ * @example
+ * ```typescript
+ * const { archive, skipped } = await exportDomain({
+ *   domain: 'com.myapp',
+ *   passphrase: userChosenPassphrase,
+ *   reason: 'Back up your saved credentials'
+ * });
+ * await writeTextFile('backup.json', archive);
+ * ```
+ */
+export async function exportDomain(
+  options: ExportDomainOptions,
+): Promise<ExportDomainResponse> {
+  return await invoke("plugin:biometry|export_domain", { options });
+}
+
+/**
+ * Restores items from an archive created by `exportDomain`, protecting them
+ * with this device's biometry. Every item must be allowed by the capability
+ * scope, otherwise nothing is imported.
+ *
+ * @param {ImportDomainOptions} options - The archive, its passphrase and the overwrite policy.
+ *
+ * @returns {Promise<ImportDomainResponse>} A promise that resolves to the
+ *                                          imported and skipped items.
+ *
+ * @throws {Error} With code `decryptionFailed` if the passphrase is wrong or
+ *                 the archive was modified.
+ *
+ * @example
+ * ```typescript
+ * const { imported, skipped } = await importDomain({
+ *   archive: await readTextFile('backup.json'),
+ *   passphrase: userChosenPassphrase
+ * });
+ * console.log(`Restored ${imported.length} items, skipped ${skipped.length}`);
+ * ```
+ */
+export async function importDomain(
+  options: ImportDomainOptions,
+): Promise<ImportDomainResponse> {
+  return await invoke("plugin:biometry|import_domain", { options });
+}
+
+/**
+ * Sends an HTTP request from Rust with a stored item inserted into one
+ * header, after biometric authentication. Only the response comes back; the
+ * item's data never reaches JavaScript. Redirects are not followed.
+ *
+ * @param {FetchWithSecretOptions} options - The item, the request and the header template.
+ *
+ * @returns {Promise<FetchResponse>} A promise that resolves to the response status, headers and body.
+ *
+ * @throws {Error} With code `scopeDenied` if the URL is not listed in the
+ *                 scope, or `httpError` if the request fails.
+ *
+ * @example
+ * ```typescript
+ * const response = await fetchWithSecret({
+ *   domain: 'com.myapp',
+ *   name: 'api_token',
+ *   reason: 'Sign in to the API',
+ *   url: 'https://api.example.com/v1/me',
+ *   secretHeader: 'Authorization',
+ *   template: 'Bearer {secret}'
+ * });
+ * const me = JSON.parse(response.body);
+ * ```
+ */
+export async function fetchWithSecret(
+  options: FetchWithSecretOptions,
+): Promise<FetchResponse> {
+  return await invoke("plugin:biometry|fetch_with_secret", { options });
+}
+
+interface RawPublicKeyResponse
+  extends Omit<PublicKeyResponse, "publicKey" | "spki" | "coseKey"> {
+  publicKey: string;
+  spki: string;
+  coseKey: string;
+}
+
+function toPublicKeyResponse(raw: RawPublicKeyResponse): PublicKeyResponse {
+  return {
+    ...raw,
+    publicKey: fromBase64(raw.publicKey),
+    spki: fromBase64(raw.spki),
+    coseKey: fromBase64(raw.coseKey),
+  };
+}
+
+/**
+ * Creates a signing key. The private key is generated on the device, stored
+ * with biometric protection and never returned to JavaScript.
+ *
+ * @param {CreateKeyOptions} options - The domain, name and algorithm of the key.
+ *
+ * @returns {Promise<PublicKeyResponse>} A promise that resolves to the new public key.
+ *
+ * @throws {Error} With code `conflict` if a key with this domain and name exists.
+ *
+ * @example
+ * ```typescript
+ * const { publicKey } = await createKey({
+ *   domain: 'com.myapp',
+ *   name: 'device_key',
+ *   alg: 'P-256'
+ * });
+ * await registerDevice(publicKey);
+ * ```
+ */
+export async function createKey(
+  options: CreateKeyOptions,
+): Promise<PublicKeyResponse> {
+  return toPublicKeyResponse(
+    await invoke("plugin:biometry|create_key", { options }),
+  );
+}
+
+/**
+ * Signs a payload with a stored key after biometric authentication.
+ * P-256 signatures are raw `r || s` (64 bytes), the encoding Web Crypto and
+ * JWS use. Ed25519 signatures are 64 bytes.
+ *
+ * @param {SignOptions} options - The key, the payload and the prompt text.
+ *
+ * @returns {Promise<Uint8Array>} A promise that resolves to the signature.
+ *
+ * @example
+ * ```typescript
+ * const signature = await sign({
+ *   domain: 'com.myapp',
+ *   name: 'device_key',
+ *   payload: new TextEncoder().encode(challenge),
+ *   reason: 'Confirm sign-in'
+ * });
+ * ```
+ */
+export async function sign(options: SignOptions): Promise<Uint8Array> {
+  const { signature } = await invoke<{ signature: string }>(
+    "plugin:biometry|sign",
+    { options: { ...options, payload: toBase64(options.payload) } },
+  );
+  return fromBase64(signature);
+}
+
+/**
+ * Encrypts a payload to a stored `ECDH-P-256` key. Only the public key is
+ * used, so this does not trigger biometric authentication. The plugin does
+ * not keep the ciphertext; store it wherever the app likes.
+ *
+ * @param {EncryptOptions} options - The key and the bytes to encrypt.
+ *
+ * @returns {Promise<string>} A promise that resolves to the ciphertext envelope.
+ *
+ * @example
+ * ```typescript
+ * const ciphertext = await encrypt({
+ *   domain: 'com.myapp',
+ *   name: 'documents_key',
+ *   plaintext: new TextEncoder().encode(document)
+ * });
+ * await writeTextFile('document.enc', ciphertext);
+ * ```
+ */
+export async function encrypt(options: EncryptOptions): Promise<string> {
+  return await invoke("plugin:biometry|encrypt", {
+    options: { ...options, plaintext: toBase64(options.plaintext) },
+  });
+}
+
+/**
+ * Decrypts a ciphertext produced by `encrypt` after biometric authentication.
+ *
+ * @param {DecryptOptions} options - The key, the ciphertext and the prompt text.
+ *
+ * @returns {Promise<Uint8Array>} A promise that resolves to the plaintext.
+ *
+ * @throws {Error} With code `decryptionFailed` if the ciphertext was made for
+ *                 another key or was modified.
+ *
+ * @example
+ * ```typescript
+ * const plaintext = await decrypt({
+ *   domain: 'com.myapp',
+ *   name: 'documents_key',
+ *   ciphertext: await readTextFile('document.enc'),
+ *   reason: 'Open your document'
+ * });
+ * ```
+ */
+export async function decrypt(options: DecryptOptions): Promise<Uint8Array> {
+  const { plaintext } = await invoke<{ plaintext: string }>(
+    "plugin:biometry|decrypt",
+    { options },
+  );
+  return fromBase64(plaintext);
+}
+
+// Bytes cross IPC as base64 rather than as an array of numbers, which is
+// several times larger.
+function toBase64(bytes: Uint8Array | number[]): string {
+  const view = bytes instanceof Uint8Array ? bytes : Uint8Array.from(bytes);
+  let binary = "";
+  for (let i = 0; i < view.length; i += 0x8000) {
+    binary += String.fromCharCode(...view.subarray(i, i + 0x8000));
+  }
+  return btoa(binary);
+}
+
+function fromBase64(text: string): Uint8Array {
+  const binary = atob(text);
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes;
+}
+
+/**
+ * Encrypts a file to an `ECDH-P-256` key, streaming it in chunks so it is
+ * never loaded into memory. Only the public half is used, so this method does
+ * not trigger biometric authentication.
+ *
+ * Both paths must be allowed by the `@tauri-apps/plugin-fs` runtime scope,
+ * e.g. picked with the dialog plugin.
+ *
+ * @param {EncryptFileOptions} options - The key and the input and output paths.
+ *
+ * @returns {Promise<void>} A promise that resolves once the output is written.
+ *
+ * @example
+ * ```typescript
+ * await encryptFile({
+ *   domain: 'com.myapp',
+ *   name: 'documents_key',
+ *   input: '/Users/me/Movies/clip.mov',
+ *   output: '/Users/me/Movies/clip.mov.enc'
+ * });
+ * ```
+ */
+export async function encryptFile(
+  options: EncryptFileOptions,
+): Promise<void> {
+  await invoke("plugin:biometry|encrypt_file", { options });
+}
+
+/**
+ * Decrypts a file written by `encryptFile` after a single biometric
+ * authentication, streaming it in chunks.
+ *
+ * @param {DecryptFileOptions} options - The key, the paths and the prompt text.
+ *
+ * @returns {Promise<void>} A promise that resolves once the output is written.
+ *
+ * @throws {Error} With code `decryptionFailed` if the file was made for
+ *                 another key, or was modified or truncated. No output is
+ *                 left behind in that case.
+ *
+ * @example
+ * ```typescript
+ * await decryptFile({
+ *   domain: 'com.myapp',
+ *   name: 'documents_key',
+ *   input: '/Users/me/Movies/clip.mov.enc',
+ *   output: '/Users/me/Movies/clip.mov',
+ *   reason: 'Open your video'
+ * });
+ * ```
+ */
+export async function decryptFile(
+  options: DecryptFileOptions,
+): Promise<void> {
+  await invoke("plugin:biometry|decrypt_file", { options });
+}
+
+/**
+ * Imports an `otpauth://` URI. The seed is stored with biometric protection
+ * and is never returned to JavaScript.
+ *
+ * @param {ImportOtpOptions} options - The domain, name and URI.
+ *
+ * @returns {Promise<OtpInfo>} A promise that resolves to the OTP's non-secret
+ *                             parameters.
+ *
+ * @throws {Error} With code `conflict` if an OTP with this domain and name
+ *                 exists and `overwrite` is not set.
+ *
+ * @example
+ * ```typescript
+ * await importOtp({
+ *   domain: 'com.myapp.2fa',
+ *   name: 'github',
+ *   uri: scannedQrText
+ * });
+ * ```
+ */
+export async function importOtp(
+  options: ImportOtpOptions,
+): Promise<OtpInfo> {
+  return await invoke("plugin:biometry|import_otp", { options });
+}
+
+/**
+ * Generates the current one-time password after biometric authentication.
+ * For HOTP, the stored counter is advanced before the code is returned.
+ *
+ * @param {GenerateOtpOptions} options - The OTP and the prompt text.
+ *
+ * @returns {Promise<OtpCode>} A promise that resolves to the code.
+ *
+ * @example
+ * ```typescript
+ * const { code } = await generateOtp({
+ *   domain: 'com.myapp.2fa',
+ *   name: 'github',
+ *   reason: 'Show your GitHub code'
+ * });
+ * ```
+ */
+export async function generateOtp(
+  options: GenerateOtpOptions,
+): Promise<OtpCode> {
+  return await invoke("plugin:biometry|generate_otp", { options });
+}
+
+/**
+ * Deletes an imported OTP. Succeeds if it does not exist.
+ *
+ * @param {OtpOptions} options - The domain and name of the OTP.
+ *
+ * @returns {Promise<void>} A promise that resolves when the OTP is deleted.
+ */
+export async function deleteOtp(options: OtpOptions): Promise<void> {
+  await invoke("plugin:biometry|delete_otp", { options });
+}
+
+/**
+ * Derives key material for a context after biometric authentication. The
+ * same domain, context and length always produce the same bytes, and nothing
+ * is stored.
+ *
+ * @param {DeriveKeyOptions} options - The domain, context, length and prompt
+ *                                     text.
+ *
+ * @returns {Promise<Uint8Array>} A promise that resolves to the derived bytes.
+ *
+ * @example
+ * ```typescript
+ * const dbKey = await deriveKey({
+ *   domain: 'com.myapp',
+ *   context: 'database',
+ *   length: 32,
+ *   reason: 'Unlock your notes'
+ * });
+ * ```
+ */
+export async function deriveKey(
+  options: DeriveKeyOptions,
+): Promise<Uint8Array> {
+  const { key } = await invoke<{ key: string }>("plugin:biometry|derive_key", {
+    options,
+  });
+  return fromBase64(key);
+}
+
+/**
+ * Returns the public half of a stored key.
+ * This method does not trigger biometric authentication.
+ *
+ * @param {KeyOptions} options - The domain and name of the key.
+ *
+ * @returns {Promise<PublicKeyResponse>} A promise that resolves to the public key.
+ *
+ * @example
+ * ```typescript
+ * const { pem, thumbprint } = await publicKey({
+ *   domain: 'com.myapp',
+ *   name: 'device_key'
+ * });
+ * await registerDevice({ id: thumbprint, key: pem });
+ * ```
+ */
+export async function publicKey(
+  options: KeyOptions,
+): Promise<PublicKeyResponse> {
+  return toPublicKeyResponse(
+    await invoke("plugin:biometry|public_key", { options }),
+  );
+}
+
+/**
+ * Windows only: returns the public key of the WebAuthn credential that
+ * protects a domain's items, read from its attestation. The response's
+ * `name` is empty and `alg` is `P-256`.
+ * This method does not trigger biometric authentication.
+ *
+ * Rejects with `itemNotFound` before the domain's first item is stored, and
+ * with `notSupported` on other platforms, for RS256 credentials, and for
+ * credentials created by an earlier plugin version.
+ *
+ * @param {CredentialKeyOptions} options - The domain.
+ *
+ * @returns {Promise<PublicKeyResponse>} A promise that resolves to the public key.
+ *
+ * @example
+ * ```typescript
+ * const { pem } = await credentialPublicKey({ domain: 'com.myapp' });
+ * ```
+ */
+export async function credentialPublicKey(
+  options: CredentialKeyOptions,
+): Promise<PublicKeyResponse> {
+  return toPublicKeyResponse(
+    await invoke("plugin:biometry|credential_public_key", { options }),
+  );
+}
+
+/**
+ * Deletes a stored key.
+ * Also resolves successfully if the key doesn't exist.
+ * This method does not trigger biometric authentication.
+ *
+ * @param {KeyOptions} options - The domain and name of the key.
+ *
+ * @returns {Promise<void>} A promise that resolves when the key is deleted.
+ *
+ * @example
+ * ```typescript
+ * await deleteKey({ domain: 'com.myapp', name: 'device_key' });
+ * ```
+ */
+export async function deleteKey(options: KeyOptions): Promise<void> {
+  await invoke("plugin:biometry|delete_key", { options });
+}
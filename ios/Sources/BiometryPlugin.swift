@@ -43,6 +43,26 @@ struct GetDataOptions: Decodable {
   let reason: String
 }
 
+struct RenameDataOptions: Decodable {
+  let domain: String
+  let name: String
+  let newDomain: String
+  let newName: String
+}
+
+struct KeyOptions: Decodable {
+  let domain: String
+  let name: String
+}
+
+struct KeyUseOptions: Decodable {
+  let domain: String
+  let name: String
+  let data: String
+  let reason: String
+  var cancelTitle: String?
+}
+
 class BiometryPlugin: Plugin {
   let authenticationErrorCodeMap: [Int: String] = [
     0: "",
@@ -95,18 +115,51 @@ class BiometryPlugin: Plugin {
     )
   }
 
+  /// `LABiometryType` as the Rust and TypeScript `BiometryType` value.
+  func biometryTypeValue(_ type: LABiometryType) -> Int {
+    switch type {
+    case .touchID:
+      return 2
+    case .faceID:
+      return 3
+    default:
+      if #available(iOS 17.0, *), type == .opticID {
+        return 5
+      }
+      return 0
+    }
+  }
+
   @objc func status(_ invoke: Invoke) {
+    // Items are stored with `userPresence`, which the passcode satisfies
+    // as well, so storage only needs the owner policy.
+    let deviceCredential = LAContext().canEvaluatePolicy(.deviceOwnerAuthentication, error: nil)
+    let capabilities: [String: Any] = [
+      "storage": deviceCredential,
+      "deviceCredential": deviceCredential,
+      "strength": self.status.available ? "strong" : "none",
+      "backend": "keychain",
+      "backendVersion": UIDevice.current.systemVersion,
+    ]
+
+    let biometryType = biometryTypeValue(self.status.biometryType)
+    let modalities = self.status.available && biometryType != 0 ? [biometryType] : []
+
     if self.status.available {
       invoke.resolve([
         "isAvailable": self.status.available,
-        "biometryType": self.status.biometryType.rawValue,
+        "biometryType": biometryType,
+        "modalities": modalities,
+        "capabilities": capabilities,
       ])
     } else {
       invoke.resolve([
         "isAvailable": self.status.available,
-        "biometryType": self.status.biometryType.rawValue,
+        "biometryType": biometryType,
+        "modalities": modalities,
         "error": self.status.errorReason ?? "",
         "errorCode": self.status.errorCode ?? "",
+        "capabilities": capabilities,
       ])
     }
   }
@@ -291,6 +344,191 @@ class BiometryPlugin: Plugin {
       }
     }
   }
+
+  // Only the item's attributes change, so the keychain moves it without
+  // reading the data and without prompting.
+  @objc func renameData(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(RenameDataOptions.self)
+
+    let query: [String: Any] = [
+      kSecClass as String: kSecClassGenericPassword,
+      kSecAttrAccount as String: args.name,
+      kSecAttrService as String: args.domain
+    ]
+    let updateAttributes: [String: Any] = [
+      kSecAttrAccount as String: args.newName,
+      kSecAttrService as String: args.newDomain
+    ]
+
+    DispatchQueue.global(qos: .userInitiated).async {
+      let status = SecItemUpdate(query as CFDictionary, updateAttributes as CFDictionary)
+
+      DispatchQueue.main.async {
+        switch status {
+        case errSecSuccess:
+          invoke.resolve()
+        case errSecDuplicateItem:
+          invoke.reject("Target item already exists", code: "conflict")
+        case errSecItemNotFound:
+          invoke.reject("Item not found", code: "itemNotFound")
+        default:
+          invoke.reject("Error renaming item in keychain: \(status)")
+        }
+      }
+    }
+  }
+
+  // P-256 keys for signing and ECDH live in the Secure Enclave. Where there
+  // is none (the simulator), creation fails with "notSupported" and the Rust
+  // side seals a software key instead.
+  @objc func createKey(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyOptions.self)
+    let tag = keyTag(args.domain, args.name)
+
+    SecItemDelete(keyQuery(tag) as CFDictionary)
+
+    guard let accessControl = SecAccessControlCreateWithFlags(
+      kCFAllocatorDefault,
+      kSecAttrAccessibleWhenUnlockedThisDeviceOnly,
+      [.privateKeyUsage, .userPresence],
+      nil
+    ) else {
+      invoke.reject("Error creating access control")
+      return
+    }
+
+    let attributes: [String: Any] = [
+      kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+      kSecAttrKeySizeInBits as String: 256,
+      kSecAttrTokenID as String: kSecAttrTokenIDSecureEnclave,
+      kSecPrivateKeyAttrs as String: [
+        kSecAttrIsPermanent as String: true,
+        kSecAttrApplicationTag as String: tag,
+        kSecAttrAccessControl as String: accessControl,
+      ],
+    ]
+
+    var error: Unmanaged<CFError>?
+    guard let privateKey = SecKeyCreateRandomKey(attributes as CFDictionary, &error) else {
+      let message = error?.takeRetainedValue().localizedDescription ?? "unknown error"
+      invoke.reject("Secure Enclave key creation failed: \(message)", code: "notSupported")
+      return
+    }
+    guard let publicKey = SecKeyCopyPublicKey(privateKey),
+      let publicData = SecKeyCopyExternalRepresentation(publicKey, nil) as Data?
+    else {
+      SecItemDelete(keyQuery(tag) as CFDictionary)
+      invoke.reject("Failed to export the public key")
+      return
+    }
+
+    invoke.resolve(["publicKey": publicData.base64EncodedString()])
+  }
+
+  // Returns an X9.62 DER signature over SHA-256 of `data`.
+  @objc func signWithKey(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyUseOptions.self)
+    guard let payload = Data(base64Encoded: args.data) else {
+      invoke.reject("Invalid data encoding", code: "invalidInput")
+      return
+    }
+
+    useKey(args, resultKey: "signature") { privateKey, error in
+      SecKeyCreateSignature(
+        privateKey, .ecdsaSignatureMessageX962SHA256, payload as CFData, &error) as Data?
+    }
+  }
+
+  // Returns the shared x-coordinate for the uncompressed SEC1 point in `data`.
+  @objc func keyAgreement(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyUseOptions.self)
+    let peerAttributes: [String: Any] = [
+      kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+      kSecAttrKeyClass as String: kSecAttrKeyClassPublic,
+      kSecAttrKeySizeInBits as String: 256,
+    ]
+    guard let peerData = Data(base64Encoded: args.data),
+      let peer = SecKeyCreateWithData(peerData as CFData, peerAttributes as CFDictionary, nil)
+    else {
+      invoke.reject("Invalid public key", code: "invalidInput")
+      return
+    }
+
+    useKey(args, resultKey: "sharedSecret") { privateKey, error in
+      SecKeyCopyKeyExchangeResult(
+        privateKey, .ecdhKeyExchangeStandard, peer, [:] as CFDictionary, &error) as Data?
+    }
+  }
+
+  @objc func deleteKey(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(KeyOptions.self)
+    let status = SecItemDelete(keyQuery(keyTag(args.domain, args.name)) as CFDictionary)
+    if status == errSecSuccess || status == errSecItemNotFound {
+      invoke.resolve()
+    } else {
+      invoke.reject("Error deleting key from keychain: \(status)")
+    }
+  }
+
+  // Looks up the key with the prompt text attached, runs `operation` off the
+  // main thread (it blocks while the prompt is shown), and resolves with the
+  // result as base64 under `resultKey`.
+  func useKey(
+    _ args: KeyUseOptions,
+    resultKey: String,
+    operation: @escaping (SecKey, inout Unmanaged<CFError>?) -> Data?
+  ) {
+    let context = LAContext()
+    context.localizedReason = args.reason
+    context.localizedCancelTitle = args.cancelTitle
+
+    var query = keyQuery(keyTag(args.domain, args.name))
+    query[kSecReturnRef as String] = true
+    query[kSecUseAuthenticationContext as String] = context
+
+    DispatchQueue.global(qos: .userInitiated).async {
+      var item: CFTypeRef?
+      let status = SecItemCopyMatching(query as CFDictionary, &item)
+      guard status == errSecSuccess, let item = item else {
+        DispatchQueue.main.async {
+          if status == errSecItemNotFound {
+            invoke.reject("No key pair found", code: "itemNotFound")
+          } else {
+            invoke.reject("Error retrieving key from keychain: \(status)")
+          }
+        }
+        return
+      }
+
+      var unmanagedError: Unmanaged<CFError>?
+      let result = operation(item as! SecKey, &unmanagedError)
+      let error = unmanagedError?.takeRetainedValue() as Error? as NSError?
+
+      DispatchQueue.main.async {
+        if let result = result {
+          invoke.resolve([resultKey: result.base64EncodedString()])
+        } else if error?.code == LAError.userCancel.rawValue
+          || error?.code == Int(errSecUserCanceled)
+        {
+          invoke.reject("User canceled", code: "userCancel")
+        } else {
+          invoke.reject("Key operation failed: \(error?.localizedDescription ?? "unknown error")")
+        }
+      }
+    }
+  }
+
+  func keyTag(_ domain: String, _ name: String) -> Data {
+    return "biometry.key:\(domain.utf8.count):\(domain):\(name)".data(using: .utf8)!
+  }
+
+  func keyQuery(_ tag: Data) -> [String: Any] {
+    return [
+      kSecClass as String: kSecClassKey,
+      kSecAttrKeyType as String: kSecAttrKeyTypeECSECPrimeRandom,
+      kSecAttrApplicationTag as String: tag,
+    ]
+  }
 }
 
 @_cdecl("init_plugin_biometry")
@@ -0,0 +1,301 @@
+//! Passphrase-encrypted archive used by `export_domain` / `import_domain` to
+//! carry one domain's items to another device.
+//!
+//! An archive is a single JSON document (format version 1):
+//!
+//! ```json
+//! {
+//!   "format": "tauri-plugin-biometry-archive",
+//!   "v": 1,
+//!   "kdf": { "alg": "argon2id", "m": 65536, "t": 3, "p": 1, "salt": "<base64, 16 bytes>" },
+//!   "aead": { "alg": "A256GCM", "nonce": "<base64, 12 bytes>" },
+//!   "domain": "com.myapp.creds",
+//!   "ct": "<base64 ciphertext || 16-byte tag>"
+//! }
+//! ```
+//!
+//! - The 32-byte key is Argon2id (version 0x13) of the UTF-8 passphrase with
+//!   `salt`, `m` KiB of memory, `t` passes and `p` lanes.
+//! - `ct` is AES-256-GCM under that key and `nonce`, with the UTF-8 bytes of
+//!   `"tauri-plugin-biometry-archive/v1/" + domain` as associated data, so
+//!   relabelling the header's `domain` makes decryption fail.
+//! - The plaintext is the JSON form of [`Contents`]:
+//!   `{ "domain", "exportedAt", "items": [{ "name", "data", "label"?,
+//!   "attributes"?, "expiresAt"? }] }`.
+//!
+//! Base64 is standard and padded. Readers reject unknown `format`, `v` or
+//! `alg` values, and KDF costs above the `MAX_*` limits, so a crafted archive
+//! can make import allocate at most 256 MiB — four times what export uses,
+//! and still within reach of a phone. All salts and nonces are fresh per
+//! export.
+
+use std::collections::BTreeMap;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use argon2::{Algorithm, Argon2, Params, Version};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::b64_field;
+use crate::error::ErrorCode;
+use crate::sensitive::SecretString;
+
+const FORMAT: &str = "tauri-plugin-biometry-archive";
+const VERSION: u8 = 1;
+const KDF_ALG: &str = "argon2id";
+const AEAD_ALG: &str = "A256GCM";
+const AAD_PREFIX: &str = "tauri-plugin-biometry-archive/v1/";
+
+// OWASP's first Argon2id recommendation bumped to 64 MiB: a backup sits on
+// disk indefinitely, so offline guessing is the threat that matters.
+const KDF_MEMORY_KIB: u32 = 64 * 1024;
+const KDF_PASSES: u32 = 3;
+const KDF_LANES: u32 = 1;
+const MAX_KDF_MEMORY_KIB: u32 = 256 * 1024;
+const MAX_KDF_PASSES: u32 = 16;
+const MAX_KDF_LANES: u32 = 16;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+/// Decrypted archive payload.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contents {
+    pub domain: String,
+    /// Export time, in milliseconds since the Unix epoch.
+    pub exported_at: u64,
+    pub items: Vec<Item>,
+}
+
+/// One exported item: its payload plus the metadata that survives a move.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    pub name: String,
+    pub data: SecretString,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    format: String,
+    v: u8,
+    kdf: Kdf,
+    aead: Aead256,
+    domain: String,
+    #[serde(with = "b64_field")]
+    ct: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Kdf {
+    alg: String,
+    m: u32,
+    t: u32,
+    p: u32,
+    #[serde(with = "b64_field")]
+    salt: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Aead256 {
+    alg: String,
+    #[serde(with = "b64_field")]
+    nonce: Vec<u8>,
+}
+
+/// Encrypts `contents` under `passphrase` and returns the archive text.
+pub fn seal(passphrase: &str, contents: &Contents) -> crate::Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::rng().fill(&mut salt);
+    rand::rng().fill(&mut nonce);
+
+    let key = derive_key(passphrase, &salt, KDF_MEMORY_KIB, KDF_PASSES, KDF_LANES)?;
+    let plaintext = Zeroizing::new(serde_json::to_vec(contents).map_err(std::io::Error::other)?);
+    let ct = cipher(&key)?
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: aad(&contents.domain).as_bytes(),
+            },
+        )
+        .map_err(|_| {
+            crate::Error::rejected(ErrorCode::EncryptionFailed, "Archive encryption failed")
+        })?;
+
+    let envelope = Envelope {
+        format: FORMAT.to_string(),
+        v: VERSION,
+        kdf: Kdf {
+            alg: KDF_ALG.to_string(),
+            m: KDF_MEMORY_KIB,
+            t: KDF_PASSES,
+            p: KDF_LANES,
+            salt: salt.to_vec(),
+        },
+        aead: Aead256 {
+            alg: AEAD_ALG.to_string(),
+            nonce: nonce.to_vec(),
+        },
+        domain: contents.domain.clone(),
+        ct,
+    };
+    serde_json::to_string(&envelope).map_err(|e| std::io::Error::other(e).into())
+}
+
+/// Parses and decrypts an archive. A wrong passphrase and a tampered
+/// archive are indistinguishable and both fail with `decryptionFailed`.
+pub fn open(passphrase: &str, archive: &str) -> crate::Result<Contents> {
+    let envelope: Envelope =
+        serde_json::from_str(archive).map_err(|e| invalid(&format!("malformed archive: {e}")))?;
+    if envelope.format != FORMAT {
+        return Err(invalid("not a biometry archive"));
+    }
+    if envelope.v != VERSION {
+        return Err(invalid(&format!(
+            "unsupported archive version {}",
+            envelope.v
+        )));
+    }
+    let Kdf { alg, m, t, p, salt } = &envelope.kdf;
+    if alg != KDF_ALG || envelope.aead.alg != AEAD_ALG {
+        return Err(invalid("unsupported archive algorithm"));
+    }
+    if *m > MAX_KDF_MEMORY_KIB || *t > MAX_KDF_PASSES || *p > MAX_KDF_LANES {
+        return Err(invalid(
+            "archive KDF parameters exceed the supported limits",
+        ));
+    }
+    if salt.len() != SALT_LEN || envelope.aead.nonce.len() != NONCE_LEN {
+        return Err(invalid("archive salt or nonce has the wrong length"));
+    }
+
+    let key = derive_key(passphrase, salt, *m, *t, *p)?;
+    let plaintext = Zeroizing::new(
+        cipher(&key)?
+            .decrypt(
+                Nonce::from_slice(&envelope.aead.nonce),
+                Payload {
+                    msg: &envelope.ct,
+                    aad: aad(&envelope.domain).as_bytes(),
+                },
+            )
+            .map_err(|_| {
+                crate::Error::rejected(
+                    ErrorCode::DecryptionFailed,
+                    "Wrong passphrase or corrupted archive",
+                )
+            })?,
+    );
+
+    let contents: Contents = serde_json::from_slice(&plaintext)
+        .map_err(|e| invalid(&format!("malformed archive payload: {e}")))?;
+    if contents.domain != envelope.domain {
+        return Err(invalid("archive payload domain does not match its header"));
+    }
+    Ok(contents)
+}
+
+fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    m: u32,
+    t: u32,
+    p: u32,
+) -> crate::Result<Zeroizing<[u8; KEY_LEN]>> {
+    let params = Params::new(m, t, p, Some(KEY_LEN))
+        .map_err(|e| invalid(&format!("archive KDF parameters: {e}")))?;
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| crate::Error::rejected(ErrorCode::InternalError, format!("argon2id: {e}")))?;
+    Ok(key)
+}
+
+fn cipher(key: &[u8; KEY_LEN]) -> crate::Result<Aes256Gcm> {
+    Aes256Gcm::new_from_slice(key)
+        .map_err(|e| crate::Error::rejected(ErrorCode::InternalError, format!("aes key init: {e}")))
+}
+
+fn aad(domain: &str) -> String {
+    format!("{AAD_PREFIX}{domain}")
+}
+
+fn invalid(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents() -> Contents {
+        Contents {
+            domain: "com.example.app".to_string(),
+            exported_at: 1_700_000_000_000,
+            items: vec![Item {
+                name: "token".to_string(),
+                data: SecretString::from("s3cret"),
+                label: Some("API token".to_string()),
+                attributes: BTreeMap::from([("env".to_string(), "prod".to_string())]),
+                expires_at: Some(1_800_000_000_000),
+            }],
+        }
+    }
+
+    fn edit(archive: &str, f: impl FnOnce(&mut Envelope)) -> String {
+        let mut envelope: Envelope = serde_json::from_str(archive).expect("archive parses");
+        f(&mut envelope);
+        serde_json::to_string(&envelope).expect("archive serializes")
+    }
+
+    #[test]
+    fn round_trip() {
+        let archive = seal("correct horse", &contents()).expect("seal");
+        let opened = open("correct horse", &archive).expect("open");
+        assert_eq!(opened.domain, "com.example.app");
+        assert_eq!(opened.exported_at, 1_700_000_000_000);
+        let item = &opened.items[0];
+        assert_eq!(item.name, "token");
+        assert_eq!(&*item.data, "s3cret");
+        assert_eq!(item.label.as_deref(), Some("API token"));
+        assert_eq!(item.attributes["env"], "prod");
+        assert_eq!(item.expires_at, Some(1_800_000_000_000));
+    }
+
+    #[test]
+    fn wrong_passphrase_and_tampering_fail_decryption() {
+        let archive = seal("correct horse", &contents()).expect("seal");
+        let flipped = edit(&archive, |e| e.ct[0] ^= 1);
+        let relabelled = edit(&archive, |e| e.domain = "com.example.other".to_string());
+        for (passphrase, archive) in [
+            ("battery staple", &archive),
+            ("correct horse", &flipped),
+            ("correct horse", &relabelled),
+        ] {
+            let err = open(passphrase, archive).err().expect("open fails");
+            assert_eq!(err.code(), ErrorCode::DecryptionFailed);
+        }
+    }
+
+    #[test]
+    fn rejects_costly_kdf_parameters_before_deriving() {
+        let archive = seal("correct horse", &contents()).expect("seal");
+        let costly = edit(&archive, |e| e.kdf.m = MAX_KDF_MEMORY_KIB + 1);
+        let err = open("correct horse", &costly).err().expect("open fails");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+    }
+}
@@ -0,0 +1,19 @@
+//! `#[serde(with = "b64_field")]` for byte fields stored as standard,
+//! padded base64 strings in JSON. Works for `Vec<u8>` and `SecretBytes`;
+//! the base64 text is zeroized either way, since for secret fields it is as
+//! sensitive as the bytes.
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
+use serde::{Deserialize, Deserializer, Serializer};
+use zeroize::Zeroizing;
+
+pub fn serialize<S: Serializer, T: AsRef<[u8]>>(v: &T, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&Zeroizing::new(B64.encode(v)))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>, T: From<Vec<u8>>>(d: D) -> Result<T, D::Error> {
+    let s = Zeroizing::new(String::deserialize(d)?);
+    B64.decode(s.as_bytes())
+        .map(T::from)
+        .map_err(serde::de::Error::custom)
+}
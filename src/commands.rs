@@ -1,15 +1,30 @@
-use tauri::{command, ipc::CommandScope, AppHandle, Runtime, WebviewWindow};
+use std::collections::BTreeMap;
 
+use tauri::{command, ipc::CommandScope, AppHandle, Manager, Runtime, Webview, WebviewWindow};
+
+use crate::error::ErrorCode;
 use crate::models::{
-    AuthOptions, DataOptions, DataResponse, GetDataOptions, RemoveDataOptions, SetDataOptions,
-    Status,
+    AuthOptions, CreateKeyOptions, CredentialKeyOptions, DataOptions, DecryptFileOptions,
+    DecryptOptions, DecryptResponse, DeriveKeyOptions, DeriveKeyResponse, EncryptFileOptions,
+    EncryptOptions, ExportDomainOptions, ExportDomainResponse, FetchResponse, GenerateOtpOptions,
+    GetDataOptions, GetDataResponse, ImportDomainOptions, ImportDomainResponse, ImportOtpOptions,
+    ItemMetadata, KeyOptions, OtpCode, OtpInfo, OtpOptions, PublicKeyResponse, PurgeExpiredOptions,
+    RemoveDataOptions, RenameDataOptions, SetDataOptions, SignOptions, SignResponse, Status,
+    StatusOptions,
 };
-use crate::scope::{self, Entry as ScopeEntry};
+use crate::scope::{self, Entry as ScopeEntry, Op};
+use crate::{archive, derive, handle, keys, metadata, otp, rust_only, secret, status};
+#[cfg(feature = "http")]
+use crate::{http, models::FetchWithSecretOptions};
 use crate::{BiometryExt, Result};
 
 #[command]
-pub async fn status<R: Runtime>(app: AppHandle<R>) -> Result<Status> {
-    app.biometry().status()
+pub async fn status<R: Runtime>(
+    options: Option<StatusOptions>,
+    app: AppHandle<R>,
+) -> Result<Status> {
+    let fresh = options.and_then(|o| o.fresh).unwrap_or(false);
+    app.state::<status::Cache>().get(&app, fresh)
 }
 
 #[command]
@@ -19,28 +34,79 @@ pub async fn authenticate<R: Runtime>(
     app: AppHandle<R>,
     window: WebviewWindow<R>,
 ) -> Result<()> {
-    app.biometry().authenticate(window, reason, options)
+    let result = app.biometry().authenticate(window, reason, options);
+    invalidate_status_on_err(&app, result)
 }
 
 #[command]
 pub async fn has_data<R: Runtime>(
     options: DataOptions,
     app: AppHandle<R>,
+    webview: Webview<R>,
     command_scope: CommandScope<ScopeEntry>,
 ) -> Result<bool> {
-    scope::check(&command_scope, &options.domain, &options.name)?;
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::List,
+        &options.domain,
+        &options.name,
+    )?;
     app.biometry().has_data(options)
 }
 
 #[command]
 pub async fn get_data<R: Runtime>(
-    options: GetDataOptions,
+    mut options: GetDataOptions,
     app: AppHandle<R>,
     window: WebviewWindow<R>,
+    webview: Webview<R>,
     command_scope: CommandScope<ScopeEntry>,
-) -> Result<DataResponse> {
-    scope::check(&command_scope, &options.domain, &options.name)?;
-    app.biometry().get_data(window, options)
+) -> Result<GetDataResponse> {
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    let handle_options = options.handle.take();
+    if let Some(handle_options) = &handle_options {
+        handle::ttl(handle_options)?;
+    }
+    // The index lets a flagged item be refused without a prompt; the marker
+    // in the payload catches the ones it doesn't know about.
+    if app
+        .state::<metadata::Store>()
+        .get(&options.domain, &options.name)
+        .is_some_and(|m| m.rust_only)
+    {
+        return Err(rust_only::denied());
+    }
+    let label = webview.label().to_string();
+    let result = {
+        let app = app.clone();
+        blocking(move || app.biometry().get_data(window, options)).await
+    };
+    let response = rust_only::for_webview(invalidate_status_on_err(&app, result)?)?;
+    let (data, handle) = match handle_options {
+        Some(handle_options) => (
+            None,
+            Some(
+                app.state::<handle::Store>()
+                    .issue(label, response.data, &handle_options)?,
+            ),
+        ),
+        None => (Some(response.data), None),
+    };
+    Ok(GetDataResponse {
+        domain: response.domain,
+        name: response.name,
+        data,
+        handle,
+    })
 }
 
 #[command]
@@ -48,18 +114,670 @@ pub async fn set_data<R: Runtime>(
     options: SetDataOptions,
     app: AppHandle<R>,
     window: WebviewWindow<R>,
+    webview: Webview<R>,
     command_scope: CommandScope<ScopeEntry>,
 ) -> Result<()> {
-    scope::check(&command_scope, &options.domain, &options.name)?;
-    app.biometry().set_data(window, options)
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Write,
+        &options.domain,
+        &options.name,
+    )?;
+    let result = {
+        let app = app.clone();
+        blocking(move || app.biometry().set_data(window, options)).await
+    };
+    invalidate_status_on_err(&app, result)
 }
 
 #[command]
 pub async fn remove_data<R: Runtime>(
     options: RemoveDataOptions,
     app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<()> {
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Delete,
+        &options.domain,
+        &options.name,
+    )?;
+    blocking(move || app.biometry().remove_data(options)).await
+}
+
+#[command]
+pub async fn rename_data<R: Runtime>(
+    options: RenameDataOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<()> {
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Delete,
+        &options.domain,
+        &options.name,
+    )?;
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Write,
+        &options.new_domain,
+        &options.new_name,
+    )?;
+    if options.domain == options.new_domain && options.name == options.new_name {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            "Source and target must differ",
+        ));
+    }
+    let result = {
+        let app = app.clone();
+        blocking(move || app.biometry().rename_data(window, options)).await
+    };
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn get_metadata<R: Runtime>(
+    options: DataOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<Option<ItemMetadata>> {
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::List,
+        &options.domain,
+        &options.name,
+    )?;
+    let store = app.state::<metadata::Store>();
+    // Items without an entry have nothing to report; asking the backend
+    // would only fail where it has no storage.
+    if store.get(&options.domain, &options.name).is_none() {
+        return Ok(None);
+    }
+    // Drop entries whose item was removed behind the plugin's back (e.g.
+    // from Keychain Access) instead of reporting metadata for nothing.
+    if !app.biometry().has_data(options.clone())? {
+        store.remove(&options.domain, &options.name)?;
+        return Ok(None);
+    }
+    Ok(store.get(&options.domain, &options.name))
+}
+
+#[command]
+pub async fn purge_expired<R: Runtime>(
+    options: PurgeExpiredOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<Vec<DataOptions>> {
+    let vars = scope::Vars::of(&webview);
+    let expired = app.state::<metadata::Store>().expired(metadata::now_ms());
+    // Items outside the caller's scope are left for a caller that is allowed
+    // to touch them, not reported as errors.
+    let candidates: Vec<_> = expired
+        .into_iter()
+        .filter(|(domain, _)| options.domain.as_ref().map_or(true, |d| d == domain))
+        .filter(|(domain, name)| {
+            scope::check(&command_scope, &vars, Op::Delete, domain, name).is_ok()
+        })
+        .collect();
+    blocking(move || {
+        let mut purged = Vec::new();
+        for (domain, name) in candidates {
+            if app.biometry().purge_if_expired(&domain, &name)? {
+                purged.push(DataOptions { domain, name });
+            }
+        }
+        Ok(purged)
+    })
+    .await
+}
+
+/// Reads each item like `get_data` and seals them into one archive. Every
+/// item goes through its own user verification, so the user is prompted
+/// once per exported item; one authentication is not shared across them.
+#[command]
+pub async fn export_domain<R: Runtime>(
+    options: ExportDomainOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<ExportDomainResponse> {
+    let vars = scope::Vars::of(&webview);
+    if options.passphrase.is_empty() {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            "Passphrase must not be empty",
+        ));
+    }
+    let store = app.state::<metadata::Store>();
+    // Without `names`, items are enumerated through the metadata index: the
+    // platform stores can't list a domain without prompting, and some can't
+    // list at all. Out-of-scope index entries are left out silently so
+    // their names don't reach the webview.
+    let names = if let Some(names) = options.names {
+        for name in &names {
+            check_item(&command_scope, &vars, Op::Read, &options.domain, name)?;
+        }
+        names
+    } else {
+        store
+            .in_domain(&options.domain)
+            .into_iter()
+            .filter(|m| scope::check(&command_scope, &vars, Op::Read, &m.domain, &m.name).is_ok())
+            .map(|m| m.name)
+            .collect()
+    };
+
+    let mut items = Vec::new();
+    let mut skipped = Vec::new();
+    let mut unindexed = Vec::new();
+    for name in names {
+        let item = DataOptions {
+            domain: options.domain.clone(),
+            name: name.clone(),
+        };
+        let meta = store.get(&options.domain, &name);
+        // Rust-only items would leave Rust inside the archive.
+        if meta.as_ref().is_some_and(|m| m.rust_only) || !app.biometry().has_data(item.clone())? {
+            skipped.push(item);
+            continue;
+        }
+        let read = GetDataOptions {
+            domain: options.domain.clone(),
+            name: name.clone(),
+            reason: options.reason.clone(),
+            cancel_title: options.cancel_title.clone(),
+            handle: None,
+        };
+        let result = {
+            let (app, window) = (app.clone(), window.clone());
+            blocking(move || app.biometry().get_data(window, read)).await
+        };
+        let response = invalidate_status_on_err(&app, result)?;
+        if response.rust_only {
+            skipped.push(item);
+            continue;
+        }
+        // Items written before expiry moved into the payload only have it
+        // in the index.
+        let (label, attributes, expires_at) = if let Some(meta) = meta {
+            let expires_at = response.expires_at.or(meta.expires_at);
+            (meta.label, meta.attributes, expires_at)
+        } else {
+            unindexed.push(item);
+            (None, BTreeMap::new(), response.expires_at)
+        };
+        items.push(archive::Item {
+            name,
+            data: response.data,
+            label,
+            attributes,
+            expires_at,
+        });
+    }
+
+    let contents = archive::Contents {
+        domain: options.domain,
+        exported_at: metadata::now_ms(),
+        items,
+    };
+    let passphrase = options.passphrase;
+    let archive = blocking(move || archive::seal(&passphrase, &contents)).await?;
+    Ok(ExportDomainResponse {
+        archive,
+        skipped,
+        unindexed,
+    })
+}
+
+#[command]
+pub async fn import_domain<R: Runtime>(
+    options: ImportDomainOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<ImportDomainResponse> {
+    let vars = scope::Vars::of(&webview);
+    let (passphrase, archive) = (options.passphrase, options.archive);
+    let contents = blocking(move || archive::open(&passphrase, &archive)).await?;
+    let overwrite = options.overwrite.unwrap_or(false);
+    let now = metadata::now_ms();
+
+    let mut response = ImportDomainResponse::default();
+    let mut writes = Vec::new();
+    // Check everything before writing anything, so an archive with one
+    // out-of-scope or malformed item is rejected as a whole.
+    for item in contents.items {
+        check_item(
+            &command_scope,
+            &vars,
+            Op::Write,
+            &contents.domain,
+            &item.name,
+        )?;
+        if item.expires_at.is_some_and(|t| t <= now) {
+            response.skipped.push(DataOptions {
+                domain: contents.domain.clone(),
+                name: item.name,
+            });
+            continue;
+        }
+        let write = SetDataOptions {
+            domain: contents.domain.clone(),
+            name: item.name,
+            data: item.data,
+            label: item.label,
+            attributes: item.attributes,
+            expires_at: item.expires_at,
+            ttl: None,
+            // Lets the existence check and the write happen atomically.
+            if_absent: Some(!overwrite),
+            if_version: None,
+            rust_only: None,
+        };
+        metadata::validate(&write)?;
+        writes.push(write);
+    }
+
+    let result = {
+        let app = app.clone();
+        blocking(move || {
+            for write in writes {
+                let item = DataOptions {
+                    domain: write.domain.clone(),
+                    name: write.name.clone(),
+                };
+                match app.biometry().set_data(window.clone(), write) {
+                    Ok(()) => response.imported.push(item),
+                    Err(e) if !overwrite && e.code() == ErrorCode::Conflict => {
+                        response.skipped.push(item);
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(response)
+        })
+        .await
+    };
+    invalidate_status_on_err(&app, result)
+}
+
+#[cfg(feature = "http")]
+#[command]
+pub async fn fetch_with_secret<R: Runtime>(
+    options: FetchWithSecretOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<FetchResponse> {
+    let vars = scope::Vars::of(&webview);
+    check_item(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    let url = http::parse_url(&options.url)?;
+    scope::check_url(&command_scope, &vars, &options.domain, &options.name, &url)?;
+    let rust_only_allowed =
+        scope::allows_rust_only(&command_scope, &vars, &options.domain, &options.name, &url);
+    let request = http::prepare(&options, url)?;
+    // As in `get_data`, unless the scope entry opted in.
+    if !rust_only_allowed
+        && app
+            .state::<metadata::Store>()
+            .get(&options.domain, &options.name)
+            .is_some_and(|m| m.rust_only)
+    {
+        return Err(rust_only::denied());
+    }
+    let read = GetDataOptions {
+        domain: options.domain,
+        name: options.name,
+        reason: options.reason,
+        cancel_title: options.cancel_title,
+        handle: None,
+    };
+    let result = {
+        let app = app.clone();
+        blocking(move || app.biometry().get_data(window, read)).await
+    };
+    let mut response = invalidate_status_on_err(&app, result)?;
+    if !rust_only_allowed {
+        response = rust_only::for_webview(response)?;
+    }
+    http::send(request, &response.data).await
+}
+
+/// Without the `http` feature there is no HTTP client, so every call is
+/// refused.
+#[cfg(not(feature = "http"))]
+#[command]
+pub async fn fetch_with_secret() -> Result<FetchResponse> {
+    Err(crate::Error::rejected(
+        ErrorCode::NotSupported,
+        "fetch_with_secret needs the plugin's `http` feature",
+    ))
+}
+
+#[command]
+pub async fn create_key<R: Runtime>(
+    options: CreateKeyOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<PublicKeyResponse> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Write,
+        &options.domain,
+        &options.name,
+    )?;
+    let result = {
+        let app = app.clone();
+        blocking(move || keys::create(&app, window, &options)).await
+    };
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn sign<R: Runtime>(
+    options: SignOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<SignResponse> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    let result = keys::sign(&app, window, options).map(|signature| SignResponse { signature });
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn encrypt<R: Runtime>(
+    options: EncryptOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<String> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    keys::encrypt(&app, &options)
+}
+
+#[command]
+pub async fn decrypt<R: Runtime>(
+    options: DecryptOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<DecryptResponse> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    let result =
+        keys::decrypt(&app, window, options).map(|plaintext| DecryptResponse { plaintext });
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn encrypt_file<R: Runtime>(
+    options: EncryptFileOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<()> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    scope::check_path(&app, &options.input)?;
+    scope::check_path(&app, &options.output)?;
+    blocking(move || keys::encrypt_file(&app, &options)).await
+}
+
+#[command]
+pub async fn decrypt_file<R: Runtime>(
+    options: DecryptFileOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<()> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    scope::check_path(&app, &options.input)?;
+    scope::check_path(&app, &options.output)?;
+    let result = {
+        let app = app.clone();
+        blocking(move || keys::decrypt_file(&app, window, options)).await
+    };
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn derive_key<R: Runtime>(
+    options: DeriveKeyOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<DeriveKeyResponse> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.context,
+    )?;
+    let result = {
+        let app = app.clone();
+        blocking(move || derive::derive(&app, window, &options)).await
+    }
+    .map(|key| DeriveKeyResponse { key });
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn public_key<R: Runtime>(
+    options: KeyOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<PublicKeyResponse> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    keys::public_key(&app, &options.domain, &options.name)
+}
+
+// Matched against `(domain, "")`, so only entries without a `name` (or
+// with `name: "*"`) grant it.
+#[command]
+pub async fn credential_public_key<R: Runtime>(
+    options: CredentialKeyOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<PublicKeyResponse> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(&command_scope, &vars, Op::Read, &options.domain, "")?;
+    keys::credential_public_key(&app, &options.domain)
+}
+
+#[command]
+pub async fn delete_key<R: Runtime>(
+    options: KeyOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<()> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Delete,
+        &options.domain,
+        &options.name,
+    )?;
+    blocking(move || keys::delete(&app, &options.domain, &options.name)).await
+}
+
+#[command]
+pub async fn import_otp<R: Runtime>(
+    options: ImportOtpOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<OtpInfo> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Write,
+        &options.domain,
+        &options.name,
+    )?;
+    let result = {
+        let app = app.clone();
+        blocking(move || otp::import(&app, window, &options)).await
+    };
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn generate_otp<R: Runtime>(
+    options: GenerateOtpOptions,
+    app: AppHandle<R>,
+    window: WebviewWindow<R>,
+    webview: Webview<R>,
+    command_scope: CommandScope<ScopeEntry>,
+) -> Result<OtpCode> {
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Read,
+        &options.domain,
+        &options.name,
+    )?;
+    let result = {
+        let app = app.clone();
+        blocking(move || otp::generate(&app, window, options)).await
+    };
+    invalidate_status_on_err(&app, result)
+}
+
+#[command]
+pub async fn delete_otp<R: Runtime>(
+    options: OtpOptions,
+    app: AppHandle<R>,
+    webview: Webview<R>,
     command_scope: CommandScope<ScopeEntry>,
 ) -> Result<()> {
-    scope::check(&command_scope, &options.domain, &options.name)?;
-    app.biometry().remove_data(options)
+    let vars = scope::Vars::of(&webview);
+    scope::check(
+        &command_scope,
+        &vars,
+        Op::Delete,
+        &options.domain,
+        &options.name,
+    )?;
+    blocking(move || otp::delete(&app, &options.domain, &options.name)).await
+}
+
+// A failed prompt can mean the biometric state changed (lockout, removed
+// enrollment), so the next `status` call probes again.
+fn invalidate_status_on_err<R: Runtime, T>(app: &AppHandle<R>, result: Result<T>) -> Result<T> {
+    if result.is_err() {
+        app.state::<status::Cache>().invalidate();
+    }
+    result
+}
+
+// Scope check for the data commands, which additionally must not reach the
+// plugin's own secrets stored alongside data items.
+fn check_item(
+    scope: &CommandScope<ScopeEntry>,
+    vars: &scope::Vars,
+    op: Op,
+    domain: &str,
+    name: &str,
+) -> Result<()> {
+    scope::check(scope, vars, op, domain, name)?;
+    secret::check_not_reserved(name)
+}
+
+// Runs CPU-heavy or long blocking work off the async runtime's worker
+// threads: the archive's Argon2id, streaming a file, and every backend call
+// that takes an item lock, since waiting for one can last as long as
+// another call's prompt.
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| crate::Error::rejected(ErrorCode::InternalError, e.to_string()))?
 }
@@ -0,0 +1,183 @@
+//! `derive_key`: deterministic key material bound to user verification.
+//!
+//! Each domain has a 32-byte root that only a successful biometric check
+//! releases, and output is `HKDF-SHA-256(ikm = root, salt = empty,
+//! info = "tauri-plugin-biometry/derive-key/v1" || len(domain) as u32 BE ||
+//! domain || context)` expanded to the requested length. The same
+//! `(domain, context, length)` always yields the same bytes, and nothing
+//! derived is stored.
+//!
+//! - Windows: the root is the domain's `WebAuthn` PRF (`hmac-secret`)
+//!   evaluated at a fixed input, so it never exists outside Hello.
+//! - Elsewhere: the root is random, generated on first use and sealed in
+//!   the platform store like a private key, in the reserved key slot
+//!   [`keys::ROOT_KEY_NAME`].
+
+use hkdf::Hkdf;
+use sha2::Sha256;
+use tauri::{AppHandle, Runtime, WebviewWindow};
+use zeroize::Zeroizing;
+
+use crate::error::ErrorCode;
+use crate::models::DeriveKeyOptions;
+use crate::sensitive::SecretBytes;
+use crate::{keys, secret};
+
+const HKDF_INFO: &[u8] = b"tauri-plugin-biometry/derive-key/v1";
+// HKDF-SHA-256 can expand to at most 255 blocks of 32 bytes.
+const MAX_LENGTH: usize = 255 * 32;
+
+/// Unlocks `options.domain`'s root — prompting for user verification — and
+/// derives `options.length` bytes for `options.context`.
+pub fn derive<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: &DeriveKeyOptions,
+) -> crate::Result<SecretBytes> {
+    secret::check_supported()?;
+    validate(options)?;
+    let root = root(app, window, options)?;
+    expand(&root[..], &options.domain, &options.context, options.length)
+}
+
+// Checked before the root is unlocked, so bad options fail without a
+// prompt.
+fn validate(options: &DeriveKeyOptions) -> crate::Result<()> {
+    if options.domain.is_empty() || options.context.is_empty() {
+        return Err(invalid("Domain and context must not be empty"));
+    }
+    if options.length == 0 || options.length > MAX_LENGTH {
+        return Err(invalid(&format!(
+            "length must be between 1 and {MAX_LENGTH} bytes"
+        )));
+    }
+    Ok(())
+}
+
+// The domain is length-prefixed in `info` so `("ab", "c")` and `("a", "bc")`
+// derive different keys.
+fn expand(root: &[u8], domain: &str, context: &str, length: usize) -> crate::Result<SecretBytes> {
+    let domain_len =
+        u32::try_from(domain.len()).map_err(|_| invalid("domain exceeds maximum length"))?;
+    let info = [
+        HKDF_INFO,
+        &domain_len.to_be_bytes(),
+        domain.as_bytes(),
+        context.as_bytes(),
+    ]
+    .concat();
+    let mut out = vec![0u8; length];
+    Hkdf::<Sha256>::new(None, root)
+        .expand(&info, &mut out)
+        .map_err(|e| crate::Error::rejected(ErrorCode::InternalError, format!("hkdf: {e}")))?;
+    Ok(SecretBytes::new(out))
+}
+
+#[cfg(target_os = "windows")]
+fn root<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: &DeriveKeyOptions,
+) -> crate::Result<Zeroizing<[u8; 32]>> {
+    use crate::BiometryExt;
+
+    // The root key slot's item name: `RESERVED_PREFIX` + `ROOT_KEY_NAME`.
+    app.biometry()
+        .prf_root(window, &options.domain, keys::RESERVED_PREFIX)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn root<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: &DeriveKeyOptions,
+) -> crate::Result<Zeroizing<[u8; 32]>> {
+    keys::root_secret(
+        app,
+        window,
+        &options.domain,
+        options.reason.clone(),
+        options.cancel_title.clone(),
+    )
+}
+
+fn invalid(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT: [u8; 32] = [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24,
+        25, 26, 27, 28, 29, 30, 31,
+    ];
+
+    fn derived(root: &[u8], domain: &str, context: &str, length: usize) -> Vec<u8> {
+        expand(root, domain, context, length)
+            .expect("derive")
+            .to_vec()
+    }
+
+    #[test]
+    fn matches_the_documented_construction() {
+        // HKDF-SHA-256 computed independently from the formula in the
+        // module docs.
+        let expected = [
+            0x54, 0x6d, 0x06, 0x6b, 0xb1, 0x40, 0xe4, 0x03, 0x61, 0x3c, 0xc2, 0x62, 0x45, 0x08,
+            0xc1, 0x46, 0xdf, 0xdf, 0xae, 0xba, 0x5b, 0xd7, 0xfe, 0xf8, 0x6e, 0x2c, 0x99, 0xc0,
+            0x90, 0x37, 0x47, 0x24,
+        ];
+        assert_eq!(derived(&ROOT, "com.example.app", "database", 32), expected);
+    }
+
+    #[test]
+    fn same_inputs_give_the_same_key() {
+        assert_eq!(
+            derived(&ROOT, "com.example.app", "database", 32),
+            derived(&ROOT, "com.example.app", "database", 32)
+        );
+    }
+
+    #[test]
+    fn any_other_input_gives_another_key() {
+        let key = derived(&ROOT, "com.example.app", "database", 32);
+        let mut other_root = ROOT;
+        other_root[0] ^= 1;
+        for other in [
+            derived(&other_root, "com.example.app", "database", 32),
+            derived(&ROOT, "com.example.other", "database", 32),
+            derived(&ROOT, "com.example.app", "files", 32),
+        ] {
+            assert_ne!(other, key);
+        }
+        assert_ne!(derived(&ROOT, "ab", "c", 32), derived(&ROOT, "a", "bc", 32));
+    }
+
+    #[test]
+    fn length_is_honoured() {
+        for length in [1, 16, 32, 33, 64, MAX_LENGTH] {
+            assert_eq!(derived(&ROOT, "d", "c", length).len(), length);
+        }
+        let options = |length| DeriveKeyOptions {
+            domain: "d".to_string(),
+            context: "c".to_string(),
+            length,
+            reason: "Unlock".to_string(),
+            cancel_title: None,
+        };
+        assert!(validate(&options(32)).is_ok());
+        for bad in [0, MAX_LENGTH + 1] {
+            assert_eq!(
+                validate(&options(bad)).expect_err("length").code(),
+                ErrorCode::InvalidInput
+            );
+        }
+        assert!(validate(&DeriveKeyOptions {
+            context: String::new(),
+            ..options(32)
+        })
+        .is_err());
+    }
+}
@@ -1,10 +1,15 @@
 use serde::de::DeserializeOwned;
 use tauri::{plugin::PluginApi, AppHandle, Runtime, WebviewWindow};
 
+use crate::error::ErrorCode;
 use crate::models::{
-    AuthOptions, DataOptions, DataResponse, GetDataOptions, RemoveDataOptions, SetDataOptions,
-    Status,
+    AuthOptions, BiometryStrength, BiometryType, Capabilities, DataOptions, DataResponse,
+    GetDataOptions, KeyAlgorithm, RemoveDataOptions, RenameDataOptions, SetDataOptions, Status,
 };
+use crate::secret;
+use crate::sensitive::SecretBytes;
+
+const UNSUPPORTED: &str = "Biometry is not supported on this platform";
 
 // Signature must match the cross-platform plugin contract — return type is
 // fixed even though desktop init can't fail.
@@ -20,13 +25,30 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
 pub struct Biometry<R: Runtime>(AppHandle<R>);
 
 impl<R: Runtime> Biometry<R> {
+    pub(crate) const fn app(&self) -> &AppHandle<R> {
+        &self.0
+    }
+
     // All desktop fallback methods just return "unsupported" without touching
     // per-instance state. Signatures match the cross-platform shape.
-    #[allow(clippy::unused_self)]
+    #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
     pub fn status(&self) -> crate::Result<Status> {
-        Err(crate::Error::from(std::io::Error::other(
-            "Biometry is not supported on this platform",
-        )))
+        Ok(Status {
+            is_available: false,
+            biometry_type: BiometryType::None,
+            error: Some(UNSUPPORTED.to_string()),
+            error_code: Some(ErrorCode::UnsupportedPlatform.to_string()),
+            modalities: Vec::new(),
+            capabilities: Capabilities {
+                storage: false,
+                device_credential: false,
+                // Only with the unprotected software store (see `secret`).
+                keys: secret::SUPPORTED,
+                strength: BiometryStrength::None,
+                backend: "none".to_string(),
+                backend_version: None,
+            },
+        })
     }
 
     #[allow(clippy::unused_self)]
@@ -36,44 +58,91 @@ impl<R: Runtime> Biometry<R> {
         _reason: String,
         _options: AuthOptions,
     ) -> crate::Result<()> {
-        Err(crate::Error::from(std::io::Error::other(
-            "Biometry is not supported on this platform",
-        )))
+        Err(unsupported())
     }
 
     #[allow(clippy::unused_self)]
-    pub fn has_data(&self, _options: DataOptions) -> crate::Result<bool> {
-        Err(crate::Error::from(std::io::Error::other(
-            "Biometry is not supported on this platform",
-        )))
+    pub(crate) fn backend_has_data(&self, _options: DataOptions) -> crate::Result<bool> {
+        Err(unsupported())
     }
 
     #[allow(clippy::unused_self)]
-    pub fn get_data(
+    pub(crate) fn backend_get_data(
         &self,
         _window: WebviewWindow<R>,
         _options: GetDataOptions,
     ) -> crate::Result<DataResponse> {
-        Err(crate::Error::from(std::io::Error::other(
-            "Biometry is not supported on this platform",
-        )))
+        Err(unsupported())
     }
 
     #[allow(clippy::unused_self)]
-    pub fn set_data(
+    pub(crate) fn backend_set_data(
         &self,
         _window: WebviewWindow<R>,
         _options: SetDataOptions,
     ) -> crate::Result<()> {
-        Err(crate::Error::from(std::io::Error::other(
-            "Biometry is not supported on this platform",
-        )))
+        Err(unsupported())
     }
 
     #[allow(clippy::unused_self)]
-    pub fn remove_data(&self, _options: RemoveDataOptions) -> crate::Result<()> {
-        Err(crate::Error::from(std::io::Error::other(
-            "Biometry is not supported on this platform",
-        )))
+    pub(crate) fn backend_remove_data(&self, _options: RemoveDataOptions) -> crate::Result<()> {
+        Err(unsupported())
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn backend_rename_data(
+        &self,
+        _window: WebviewWindow<R>,
+        _options: RenameDataOptions,
+    ) -> crate::Result<()> {
+        Err(unsupported())
+    }
+
+    // There is no key store here, so every key is sealed in the software
+    // store and the other `platform_*` methods are never reached.
+    #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
+    pub(crate) fn create_platform_key(
+        &self,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _name: &str,
+        _alg: KeyAlgorithm,
+    ) -> crate::Result<Option<Vec<u8>>> {
+        Ok(None)
     }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn platform_sign(
+        &self,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _name: &str,
+        _payload: &[u8],
+        _reason: String,
+        _cancel_title: Option<String>,
+    ) -> crate::Result<Vec<u8>> {
+        Err(unsupported())
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn platform_key_agreement(
+        &self,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _name: &str,
+        _peer: &[u8],
+        _reason: String,
+        _cancel_title: Option<String>,
+    ) -> crate::Result<SecretBytes> {
+        Err(unsupported())
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn delete_platform_key(&self, _domain: &str, _name: &str) -> crate::Result<()> {
+        Err(unsupported())
+    }
+}
+
+fn unsupported() -> crate::Error {
+    crate::Error::rejected(ErrorCode::UnsupportedPlatform, UNSUPPORTED)
 }
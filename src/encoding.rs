@@ -0,0 +1,370 @@
+//! Standard encodings of the public keys managed by `keys`: SPKI (DER and
+//! PEM), JWK, `COSE_Key` and the RFC 7638 JWK thumbprint.
+//!
+//! Both supported algorithms have a fixed-size public key, so the DER and
+//! CBOR structures are fixed prefixes around the key bytes rather than the
+//! output of a general encoder.
+
+use base64::{
+    engine::general_purpose::{STANDARD as B64, URL_SAFE_NO_PAD as B64URL},
+    Engine as _,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::models::KeyAlgorithm;
+
+// SEQUENCE { SEQUENCE { id-ecPublicKey, prime256v1 }, BIT STRING (65 bytes) }
+const P256_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+// SEQUENCE { SEQUENCE { id-Ed25519 }, BIT STRING (32 bytes) } — RFC 8410.
+const ED25519_SPKI_PREFIX: &[u8] = &[
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+const PEM_LINE_LEN: usize = 64;
+
+/// Public key as a JSON Web Key (RFC 7517 / RFC 8037).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    pub alg: String,
+    /// The RFC 7638 thumbprint.
+    pub kid: String,
+}
+
+/// DER-encoded `SubjectPublicKeyInfo`. `raw` is the key as returned by
+/// `public_key`: an uncompressed SEC1 point for P-256, 32 bytes for Ed25519.
+pub fn spki(alg: KeyAlgorithm, raw: &[u8]) -> Vec<u8> {
+    let prefix = match alg {
+        KeyAlgorithm::P256 | KeyAlgorithm::EcdhP256 => P256_SPKI_PREFIX,
+        KeyAlgorithm::Ed25519 => ED25519_SPKI_PREFIX,
+    };
+    [prefix, raw].concat()
+}
+
+/// `-----BEGIN PUBLIC KEY-----` PEM of [`spki`], with LF line endings.
+pub fn pem(spki: &[u8]) -> String {
+    let body = B64.encode(spki);
+    let mut pem = String::from("-----BEGIN PUBLIC KEY-----\n");
+    for line in body.as_bytes().chunks(PEM_LINE_LEN) {
+        pem.push_str(&String::from_utf8_lossy(line));
+        pem.push('\n');
+    }
+    pem.push_str("-----END PUBLIC KEY-----\n");
+    pem
+}
+
+pub fn jwk(alg: KeyAlgorithm, raw: &[u8]) -> Jwk {
+    let kid = thumbprint(alg, raw);
+    match alg {
+        KeyAlgorithm::P256 | KeyAlgorithm::EcdhP256 => {
+            let (x, y) = p256_coordinates(raw);
+            Jwk {
+                kty: "EC".to_string(),
+                crv: "P-256".to_string(),
+                x: B64URL.encode(x),
+                y: Some(B64URL.encode(y)),
+                alg: if alg == KeyAlgorithm::P256 {
+                    "ES256"
+                } else {
+                    "ECDH-ES"
+                }
+                .to_string(),
+                kid,
+            }
+        }
+        KeyAlgorithm::Ed25519 => Jwk {
+            kty: "OKP".to_string(),
+            crv: "Ed25519".to_string(),
+            x: B64URL.encode(raw),
+            y: None,
+            alg: "EdDSA".to_string(),
+            kid,
+        },
+    }
+}
+
+/// RFC 7638 thumbprint: base64url SHA-256 of the required JWK members in
+/// lexicographic order, without whitespace.
+pub fn thumbprint(alg: KeyAlgorithm, raw: &[u8]) -> String {
+    let canonical = match alg {
+        KeyAlgorithm::P256 | KeyAlgorithm::EcdhP256 => {
+            let (x, y) = p256_coordinates(raw);
+            format!(
+                r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#,
+                B64URL.encode(x),
+                B64URL.encode(y)
+            )
+        }
+        KeyAlgorithm::Ed25519 => {
+            format!(
+                r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#,
+                B64URL.encode(raw)
+            )
+        }
+    };
+    B64URL.encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// `COSE_Key` (RFC 9052 / RFC 9053) in deterministic CBOR: map keys in
+/// ascending encoded order `1, 3, -1, -2[, -3]`.
+pub fn cose_key(alg: KeyAlgorithm, raw: &[u8]) -> Vec<u8> {
+    match alg {
+        KeyAlgorithm::P256 | KeyAlgorithm::EcdhP256 => {
+            let (x, y) = p256_coordinates(raw);
+            // {1: 2 (EC2), 3: -7 (ES256) or -25 (ECDH-ES + HKDF-256),
+            //  -1: 1 (P-256), -2: x, -3: y}
+            let mut out = vec![0xa5, 0x01, 0x02, 0x03];
+            if alg == KeyAlgorithm::P256 {
+                out.push(0x26);
+            } else {
+                out.extend_from_slice(&[0x38, 0x18]);
+            }
+            out.extend_from_slice(&[0x20, 0x01]);
+            out.extend_from_slice(&[0x21, 0x58, 0x20]);
+            out.extend_from_slice(x);
+            out.extend_from_slice(&[0x22, 0x58, 0x20]);
+            out.extend_from_slice(y);
+            out
+        }
+        KeyAlgorithm::Ed25519 => {
+            // {1: 1 (OKP), 3: -8 (EdDSA), -1: 6 (Ed25519), -2: x}
+            let mut out = vec![0xa4, 0x01, 0x01, 0x03, 0x27, 0x20, 0x06];
+            out.extend_from_slice(&[0x21, 0x58, 0x20]);
+            out.extend_from_slice(raw);
+            out
+        }
+    }
+}
+
+/// The credential public key in `WebAuthn` authenticator data, as an
+/// uncompressed SEC1 point. `None` unless the data carries attested
+/// credential data with an ES256 `COSE_Key`.
+// Only the Windows backend creates WebAuthn credentials.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn attested_p256_key(auth_data: &[u8]) -> Option<Vec<u8>> {
+    // rpIdHash (32) || flags (1) || signCount (4) || aaguid (16) ||
+    // credentialIdLength (2) || credentialId || COSE_Key [|| extensions]
+    const FLAGS: usize = 32;
+    const CREDENTIAL_ID_LEN: usize = 53;
+    const ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+
+    if auth_data.get(FLAGS)? & ATTESTED_CREDENTIAL_DATA == 0 {
+        return None;
+    }
+    let len = auth_data.get(CREDENTIAL_ID_LEN..CREDENTIAL_ID_LEN + 2)?;
+    let start = CREDENTIAL_ID_LEN + 2 + usize::from(u16::from_be_bytes([len[0], len[1]]));
+    p256_from_cose_key(auth_data.get(start..)?)
+}
+
+/// The inverse of [`cose_key`] for ES256 keys: the uncompressed SEC1 point
+/// of an EC2 / P-256 `COSE_Key`. Bytes after the key are ignored.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub fn p256_from_cose_key(cose: &[u8]) -> Option<Vec<u8>> {
+    let mut cbor = Cbor(cose);
+    let (mut kty, mut alg, mut crv, mut x, mut y) = (None, None, None, None, None);
+    for _ in 0..cbor.head(MAJOR_MAP)? {
+        match cbor.int()? {
+            1 => kty = Some(cbor.int()?),
+            3 => alg = Some(cbor.int()?),
+            -1 => crv = Some(cbor.int()?),
+            -2 => x = Some(cbor.bytes()?),
+            -3 => y = Some(cbor.bytes()?),
+            _ => return None,
+        }
+    }
+    // kty EC2, alg ES256, crv P-256.
+    if (kty, alg, crv) != (Some(2), Some(-7), Some(1)) {
+        return None;
+    }
+    let (x, y) = (x?, y?);
+    (x.len() == 32 && y.len() == 32).then(|| [&[0x04], x, y].concat())
+}
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_MAP: u8 = 5;
+
+// Just enough of a CBOR reader for `COSE_Key` maps whose values are
+// integers or byte strings. Anything else fails the parse.
+struct Cbor<'a>(&'a [u8]);
+
+impl<'a> Cbor<'a> {
+    // Reads an item head of the given major type and returns its argument.
+    fn head(&mut self, major: u8) -> Option<u64> {
+        let (major_type, argument) = self.any_head()?;
+        (major_type == major).then_some(argument)
+    }
+
+    fn any_head(&mut self) -> Option<(u8, u64)> {
+        let (&first, rest) = self.0.split_first()?;
+        let width = match first & 0x1f {
+            info @ 0..=23 => {
+                self.0 = rest;
+                return Some((first >> 5, u64::from(info)));
+            }
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return None,
+        };
+        let argument = rest
+            .get(..width)?
+            .iter()
+            .fold(0u64, |acc, &b| acc << 8 | u64::from(b));
+        self.0 = &rest[width..];
+        Some((first >> 5, argument))
+    }
+
+    fn int(&mut self) -> Option<i64> {
+        match self.any_head()? {
+            (MAJOR_UNSIGNED, n) => i64::try_from(n).ok(),
+            (MAJOR_NEGATIVE, n) => i64::try_from(n).ok().map(|n| -1 - n),
+            _ => None,
+        }
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let len = usize::try_from(self.head(MAJOR_BYTES)?).ok()?;
+        let bytes = self.0.get(..len)?;
+        self.0 = &self.0[len..];
+        Some(bytes)
+    }
+}
+
+// Splits an uncompressed SEC1 point (`0x04 || x || y`) into its coordinates.
+fn p256_coordinates(raw: &[u8]) -> (&[u8], &[u8]) {
+    let coordinates = raw.get(1..).unwrap_or_default();
+    coordinates.split_at(coordinates.len() / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 7517 Appendix A.1.
+    const P256_X: &str = "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4";
+    const P256_Y: &str = "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM";
+
+    fn p256_point() -> Vec<u8> {
+        let x = B64URL.decode(P256_X).expect("x");
+        let y = B64URL.decode(P256_Y).expect("y");
+        [&[0x04][..], &x, &y].concat()
+    }
+
+    #[test]
+    fn ed25519_thumbprint_matches_rfc_8037() {
+        // RFC 8037 Appendix A.2 / A.3.
+        let x = B64URL
+            .decode("11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo")
+            .expect("x");
+        assert_eq!(
+            thumbprint(KeyAlgorithm::Ed25519, &x),
+            "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+        );
+    }
+
+    #[test]
+    fn p256_thumbprint_and_jwk() {
+        let point = p256_point();
+        let expected = "cn-I_WNMClehiVp51i_0VpOENW1upEerA8sEam5hn-s";
+        assert_eq!(thumbprint(KeyAlgorithm::P256, &point), expected);
+        // The thumbprint covers the key, not its use.
+        assert_eq!(thumbprint(KeyAlgorithm::EcdhP256, &point), expected);
+
+        let jwk = jwk(KeyAlgorithm::P256, &point);
+        assert_eq!(
+            (jwk.x.as_str(), jwk.y.as_deref(), jwk.kid.as_str()),
+            (P256_X, Some(P256_Y), expected)
+        );
+    }
+
+    #[test]
+    fn p256_cose_key_encoding() {
+        let point = p256_point();
+        let encoded = cose_key(KeyAlgorithm::P256, &point);
+        let expected = [
+            &[0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x58, 0x20][..],
+            &point[1..33],
+            &[0x22, 0x58, 0x20],
+            &point[33..],
+        ]
+        .concat();
+        assert_eq!(encoded, expected);
+        assert_eq!(p256_from_cose_key(&encoded), Some(point.clone()));
+
+        // ECDH-ES + HKDF-256 is -25, a one-byte negative integer argument.
+        let ecdh = cose_key(KeyAlgorithm::EcdhP256, &point);
+        assert_eq!(&ecdh[..7], &[0xa5, 0x01, 0x02, 0x03, 0x38, 0x18, 0x20]);
+        assert_eq!(p256_from_cose_key(&ecdh), None);
+    }
+
+    #[test]
+    fn ed25519_cose_key_and_spki_encoding() {
+        let x = [0x11; 32];
+        let cose = cose_key(KeyAlgorithm::Ed25519, &x);
+        assert_eq!(
+            &cose[..10],
+            &[0xa4, 0x01, 0x01, 0x03, 0x27, 0x20, 0x06, 0x21, 0x58, 0x20]
+        );
+        assert_eq!(&cose[10..], &x);
+        assert_eq!(p256_from_cose_key(&cose), None);
+
+        let spki = spki(KeyAlgorithm::Ed25519, &x);
+        assert_eq!(spki.len(), 44);
+        assert!(pem(&spki).starts_with("-----BEGIN PUBLIC KEY-----\nMCowBQYDK2VwAyEA"));
+    }
+
+    #[test]
+    fn reads_the_credential_key_from_authenticator_data() {
+        let point = p256_point();
+        let credential_id = [0xab; 20];
+        let mut auth_data = vec![0u8; 32];
+        // UP | UV | AT | ED
+        auth_data.push(0x01 | 0x04 | 0x40 | 0x80);
+        auth_data.extend_from_slice(&[0, 0, 0, 0]);
+        auth_data.extend_from_slice(&[0u8; 16]);
+        auth_data.extend_from_slice(&[0, 20]);
+        auth_data.extend_from_slice(&credential_id);
+        auth_data.extend_from_slice(&cose_key(KeyAlgorithm::P256, &point));
+        // {"hmac-secret": true}
+        auth_data.extend_from_slice(&[0xa1, 0x6b]);
+        auth_data.extend_from_slice(b"hmac-secret");
+        auth_data.push(0xf5);
+
+        assert_eq!(attested_p256_key(&auth_data), Some(point));
+
+        let mut no_attested_data = auth_data.clone();
+        no_attested_data[32] &= !0x40;
+        assert_eq!(attested_p256_key(&no_attested_data), None);
+        assert_eq!(attested_p256_key(&auth_data[..70]), None);
+    }
+
+    #[test]
+    fn rejects_cose_keys_that_are_not_es256() {
+        // RS256 (-257): {1: 3, 3: -257, -1: n, -2: e}
+        let rsa = [
+            0xa4, 0x01, 0x03, 0x03, 0x39, 0x01, 0x00, 0x20, 0x41, 0x01, 0x21, 0x43, 0x01, 0x00,
+            0x01,
+        ];
+        assert_eq!(p256_from_cose_key(&rsa), None);
+
+        // Right labels, P-384 curve.
+        let mut p384 = cose_key(KeyAlgorithm::P256, &p256_point());
+        p384[8] = 0x02;
+        assert_eq!(p256_from_cose_key(&p384), None);
+
+        // Truncated coordinates.
+        let full = cose_key(KeyAlgorithm::P256, &p256_point());
+        assert_eq!(p256_from_cose_key(&full[..full.len() - 1]), None);
+        assert_eq!(p256_from_cose_key(&[]), None);
+    }
+}
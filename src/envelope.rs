@@ -0,0 +1,368 @@
+//! Ciphertext envelope for `encrypt` / `decrypt`: ECDH-ES on P-256 with
+//! HKDF-SHA-256 feeding AES-256-GCM.
+//!
+//! Laid out like the Windows storage `Blob` — a JSON object of base64
+//! fields — and bound the same way, but a format of its own: the key comes
+//! from ECDH with an ephemeral key rather than from a `WebAuthn` PRF, so
+//! the envelope carries `epk` and `kid` where the blob has `salt` and
+//! `cred`. The AES-GCM associated data is the JSON of
+//! `{ v, domain, name, epk, kid }`, so an envelope only opens under the key
+//! it was made for, and only when presented with that key's
+//! `(domain, name)`.
+//!
+//! ```json
+//! { "v": 1, "kid": "<RFC 7638 thumbprint>", "epk": "<base64, 65 bytes>",
+//!   "iv": "<base64, 12 bytes>", "ct": "<base64 ciphertext || 16-byte tag>" }
+//! ```
+//!
+//! The AES key is `HKDF-SHA-256(ikm = ECDH x-coordinate, salt = empty,
+//! info = "tauri-plugin-biometry/encrypt/v1" || epk || recipient public key)`.
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use hkdf::Hkdf;
+use p256::{ecdh::diffie_hellman, elliptic_curve::sec1::ToEncodedPoint, PublicKey, SecretKey};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::b64_field;
+use crate::error::ErrorCode;
+use crate::sensitive::SecretBytes;
+
+const VERSION: u8 = 1;
+const HKDF_INFO: &[u8] = b"tauri-plugin-biometry/encrypt/v1";
+const NONCE_LEN: usize = 12;
+const KEY_LEN: usize = 32;
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    v: u8,
+    kid: String,
+    #[serde(with = "b64_field")]
+    epk: Vec<u8>,
+    #[serde(with = "b64_field")]
+    iv: Vec<u8>,
+    #[serde(with = "b64_field")]
+    ct: Vec<u8>,
+}
+
+/// Encrypts `plaintext` to the P-256 public key `recipient` (uncompressed
+/// SEC1) and returns the envelope text. Needs no private key, so no prompt.
+pub fn seal(
+    domain: &str,
+    name: &str,
+    kid: &str,
+    recipient: &[u8],
+    plaintext: &[u8],
+) -> crate::Result<String> {
+    let recipient = PublicKey::from_sec1_bytes(recipient)
+        .map_err(|e| internal(&format!("recipient key: {e}")))?;
+    let ephemeral = random_secret();
+    let epk = ephemeral
+        .public_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let shared = diffie_hellman(ephemeral.to_nonzero_scalar(), recipient.as_affine());
+    let key = derive_key(shared.raw_secret_bytes(), &epk, &recipient)?;
+
+    let mut iv = [0u8; NONCE_LEN];
+    rand::rng().fill(&mut iv);
+    let ct = Aes256Gcm::new_from_slice(key.as_ref())
+        .map_err(|e| internal(&format!("aes key init: {e}")))?
+        .encrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: plaintext,
+                aad: &aad_for(domain, name, &epk, kid)?,
+            },
+        )
+        .map_err(|e| {
+            crate::Error::rejected(ErrorCode::EncryptionFailed, format!("aes-gcm encrypt: {e}"))
+        })?;
+
+    serde_json::to_string(&Envelope {
+        v: VERSION,
+        kid: kid.to_string(),
+        epk,
+        iv: iv.to_vec(),
+        ct,
+    })
+    .map_err(|e| internal(&format!("encode envelope: {e}")))
+}
+
+/// Opens an envelope addressed to `recipient`. `agree` performs ECDH
+/// between the recipient's private key and the envelope's ephemeral public
+/// key (uncompressed SEC1) and returns the shared x-coordinate; it is only
+/// called once the envelope has been checked, since it may prompt.
+pub fn open(
+    domain: &str,
+    name: &str,
+    kid: &str,
+    recipient: &[u8],
+    agree: impl FnOnce(&[u8]) -> crate::Result<Zeroizing<[u8; KEY_LEN]>>,
+    envelope: &str,
+) -> crate::Result<SecretBytes> {
+    let envelope: Envelope = serde_json::from_str(envelope).map_err(|e| {
+        crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            format!("malformed ciphertext: {e}"),
+        )
+    })?;
+    if envelope.v != VERSION {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            format!("unsupported ciphertext version {}", envelope.v),
+        ));
+    }
+    if envelope.kid != kid {
+        return Err(crate::Error::rejected(
+            ErrorCode::DecryptionFailed,
+            "Ciphertext was encrypted to a different key",
+        ));
+    }
+    if envelope.iv.len() != NONCE_LEN {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            "ciphertext iv has the wrong length",
+        ));
+    }
+
+    let recipient = PublicKey::from_sec1_bytes(recipient)
+        .map_err(|e| internal(&format!("recipient key: {e}")))?;
+    PublicKey::from_sec1_bytes(&envelope.epk).map_err(|e| {
+        crate::Error::rejected(ErrorCode::InvalidInput, format!("ephemeral key: {e}"))
+    })?;
+    let shared = agree(&envelope.epk)?;
+    let key = derive_key(shared.as_ref(), &envelope.epk, &recipient)?;
+
+    Aes256Gcm::new_from_slice(key.as_ref())
+        .map_err(|e| internal(&format!("aes key init: {e}")))?
+        .decrypt(
+            Nonce::from_slice(&envelope.iv),
+            Payload {
+                msg: &envelope.ct,
+                aad: &aad_for(domain, name, &envelope.epk, kid)?,
+            },
+        )
+        .map(SecretBytes::new)
+        .map_err(|e| {
+            crate::Error::rejected(ErrorCode::DecryptionFailed, format!("aes-gcm decrypt: {e}"))
+        })
+}
+
+fn derive_key(
+    shared: &[u8],
+    epk: &[u8],
+    recipient: &PublicKey,
+) -> crate::Result<Zeroizing<[u8; KEY_LEN]>> {
+    let info = [HKDF_INFO, epk, recipient.to_encoded_point(false).as_bytes()].concat();
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Hkdf::<Sha256>::new(None, shared)
+        .expand(&info, key.as_mut())
+        .map_err(|e| internal(&format!("hkdf: {e}")))?;
+    Ok(key)
+}
+
+// Mirrors the storage `Blob` AAD: the full logical identity of the
+// ciphertext, so it can't be replayed under another key name or domain.
+fn aad_for(domain: &str, name: &str, epk: &[u8], kid: &str) -> crate::Result<Vec<u8>> {
+    #[derive(Serialize)]
+    struct Aad<'a> {
+        v: u8,
+        domain: &'a str,
+        name: &'a str,
+        #[serde(with = "b64_field")]
+        epk: Vec<u8>,
+        kid: &'a str,
+    }
+    serde_json::to_vec(&Aad {
+        v: VERSION,
+        domain,
+        name,
+        epk: epk.to_vec(),
+        kid,
+    })
+    .map_err(|e| internal(&format!("aad: {e}")))
+}
+
+// Same redraw-on-out-of-range approach as key generation in `keys`.
+fn random_secret() -> SecretKey {
+    loop {
+        let mut d = Zeroizing::new([0u8; KEY_LEN]);
+        rand::rng().fill(d.as_mut());
+        if let Ok(secret) = SecretKey::from_bytes(&(*d).into()) {
+            return secret;
+        }
+    }
+}
+
+fn internal(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InternalError, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOMAIN: &str = "com.example.app";
+    const NAME: &str = "documents";
+    const KID: &str = "kid";
+
+    struct Recipient {
+        secret: SecretKey,
+        public_key: Vec<u8>,
+    }
+
+    impl Recipient {
+        fn new() -> Self {
+            let secret = random_secret();
+            let public_key = secret
+                .public_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec();
+            Self { secret, public_key }
+        }
+
+        fn agree(&self, epk: &[u8]) -> Zeroizing<[u8; KEY_LEN]> {
+            let epk = PublicKey::from_sec1_bytes(epk).expect("epk");
+            let shared = diffie_hellman(self.secret.to_nonzero_scalar(), epk.as_affine());
+            let mut out = Zeroizing::new([0u8; KEY_LEN]);
+            out.copy_from_slice(shared.raw_secret_bytes());
+            out
+        }
+
+        fn open(
+            &self,
+            domain: &str,
+            name: &str,
+            kid: &str,
+            envelope: &str,
+        ) -> crate::Result<SecretBytes> {
+            open(
+                domain,
+                name,
+                kid,
+                &self.public_key,
+                |epk| Ok(self.agree(epk)),
+                envelope,
+            )
+        }
+    }
+
+    fn tampered(envelope: &str, edit: impl FnOnce(&mut Envelope)) -> String {
+        let mut parsed: Envelope = serde_json::from_str(envelope).expect("envelope");
+        edit(&mut parsed);
+        serde_json::to_string(&parsed).expect("encode")
+    }
+
+    #[test]
+    fn round_trip() {
+        let recipient = Recipient::new();
+        let envelope =
+            seal(DOMAIN, NAME, KID, &recipient.public_key, b"attack at dawn").expect("seal");
+        let plaintext = recipient.open(DOMAIN, NAME, KID, &envelope).expect("open");
+        assert_eq!(&*plaintext, b"attack at dawn");
+
+        // Every envelope has its own ephemeral key and nonce.
+        let again =
+            seal(DOMAIN, NAME, KID, &recipient.public_key, b"attack at dawn").expect("seal");
+        assert_ne!(envelope, again);
+    }
+
+    #[test]
+    fn tampering_fails_decryption() {
+        let recipient = Recipient::new();
+        let envelope = seal(DOMAIN, NAME, KID, &recipient.public_key, b"secret").expect("seal");
+        let fails = |result: crate::Result<SecretBytes>| {
+            assert_eq!(
+                result.expect_err("opened").code(),
+                ErrorCode::DecryptionFailed
+            );
+        };
+
+        fails(recipient.open(DOMAIN, NAME, KID, &tampered(&envelope, |e| e.ct[0] ^= 1)));
+        fails(recipient.open(DOMAIN, NAME, KID, &tampered(&envelope, |e| e.iv[0] ^= 1)));
+        let last = |e: &mut Envelope| {
+            let tag = e.ct.len() - 1;
+            e.ct[tag] ^= 1;
+        };
+        fails(recipient.open(DOMAIN, NAME, KID, &tampered(&envelope, last)));
+        // A different ephemeral key derives a different AES key.
+        let other_epk = Recipient::new().public_key;
+        fails(recipient.open(
+            DOMAIN,
+            NAME,
+            KID,
+            &tampered(&envelope, |e| e.epk = other_epk),
+        ));
+
+        // The AAD binds the key's identity.
+        fails(recipient.open("com.example.other", NAME, KID, &envelope));
+        fails(recipient.open(DOMAIN, "other", KID, &envelope));
+        fails(recipient.open(
+            DOMAIN,
+            NAME,
+            "other-kid",
+            &tampered(&envelope, |e| {
+                e.kid = "other-kid".to_string();
+            }),
+        ));
+    }
+
+    #[test]
+    fn wrong_key_fails_without_prompting() {
+        let recipient = Recipient::new();
+        let envelope = seal(DOMAIN, NAME, KID, &recipient.public_key, b"secret").expect("seal");
+
+        // Another kid is refused before the (prompting) key agreement.
+        let err = open(
+            DOMAIN,
+            NAME,
+            "other-kid",
+            &recipient.public_key,
+            |_| -> crate::Result<_> { panic!("agreed with the wrong key") },
+            &envelope,
+        )
+        .expect_err("opened");
+        assert_eq!(err.code(), ErrorCode::DecryptionFailed);
+
+        // Another private key under the same kid fails authentication.
+        let other = Recipient::new();
+        let err = open(
+            DOMAIN,
+            NAME,
+            KID,
+            &recipient.public_key,
+            |epk| Ok(other.agree(epk)),
+            &envelope,
+        )
+        .expect_err("opened");
+        assert_eq!(err.code(), ErrorCode::DecryptionFailed);
+    }
+
+    #[test]
+    fn rejects_malformed_envelopes() {
+        let recipient = Recipient::new();
+        let envelope = seal(DOMAIN, NAME, KID, &recipient.public_key, b"secret").expect("seal");
+        let invalid = |envelope: &str| {
+            assert_eq!(
+                recipient
+                    .open(DOMAIN, NAME, KID, envelope)
+                    .expect_err("opened")
+                    .code(),
+                ErrorCode::InvalidInput
+            );
+        };
+
+        invalid("not json");
+        invalid(&tampered(&envelope, |e| e.v = 2));
+        invalid(&tampered(&envelope, |e| e.iv.truncate(8)));
+        invalid(&tampered(&envelope, |e| e.epk = vec![0x04; 65]));
+    }
+}
@@ -1,7 +1,100 @@
-use serde::{ser::Serializer, Serialize};
+use std::path::PathBuf;
+
+use serde::{
+    ser::{SerializeStruct, Serializer},
+    Deserialize, Serialize,
+};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Every error code the plugin reports, on every platform. Serialized as
+/// the camelCase string the frontend sees in `BiometryError.code`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorCode {
+    /// The user dismissed the prompt.
+    UserCancel,
+    /// The biometric or credential check failed.
+    AuthenticationFailed,
+    /// No usable biometric hardware, or it is disabled.
+    BiometryNotAvailable,
+    /// The hardware is present but nothing is enrolled.
+    BiometryNotEnrolled,
+    /// Too many failed attempts.
+    BiometryLockout,
+    /// The system interrupted the prompt (another app, device busy).
+    SystemCancel,
+    /// The app invalidated the prompt.
+    AppCancel,
+    /// The authentication context was invalidated.
+    InvalidContext,
+    /// The prompt would need UI but UI was disallowed.
+    NotInteractive,
+    /// The device has no passcode set.
+    PasscodeNotSet,
+    /// The user chose the fallback button.
+    UserFallback,
+    /// A device credential was requested but none is set up (Android).
+    NoDeviceCredential,
+    /// The keychain item needs authentication but UI was disallowed (macOS).
+    AuthenticationRequired,
+    /// The item, key or OTP does not exist or has expired.
+    ItemNotFound,
+    /// A conditional write or rename found a different item state.
+    Conflict,
+    /// The stored item predates the current storage format and must be
+    /// removed and stored again (Windows).
+    DataNeedsReenrollment,
+    /// The platform credential backing a domain could not be created
+    /// (Windows).
+    CredentialCreationFailed,
+    /// Encrypting data failed.
+    EncryptionFailed,
+    /// Wrong passphrase or key, or the ciphertext was modified.
+    DecryptionFailed,
+    /// Any other keychain failure (macOS).
+    KeychainError,
+    /// `getData`, or `fetchWithSecret` without a scope opt-in, was called
+    /// for an item stored with `rustOnly`.
+    RustOnly,
+    /// A secret handle is unknown, expired, used or foreign.
+    InvalidHandle,
+    /// The options failed validation.
+    InvalidInput,
+    /// The item, URL or path is outside the caller's scope.
+    ScopeDenied,
+    /// The operation is not available on this platform.
+    NotSupported,
+    /// The plugin has no biometry backend for this OS (Linux).
+    UnsupportedPlatform,
+    /// An outgoing HTTP request failed.
+    HttpError,
+    /// An operation did not finish in time.
+    Timeout,
+    /// Reading or writing one of the plugin's files failed.
+    IoError,
+    /// An unexpected failure inside the plugin or the platform API.
+    InternalError,
+    /// A native error with no code this plugin recognizes.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The code as it appears on the wire.
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.serialize(f)
+    }
+}
+
+impl ErrorCode {
+    /// Maps a code string from a native backend. Codes this plugin doesn't
+    /// know become `Unknown` rather than failing.
+    fn parse(code: &str) -> Self {
+        serde_json::from_value(serde_json::Value::from(code)).unwrap_or(Self::Unknown)
+    }
+}
+
 /// Replica of the `tauri::plugin::mobile::ErrorResponse` for desktop platforms.
 #[cfg(desktop)]
 #[derive(Debug, thiserror::Error, Clone, serde::Deserialize)]
@@ -46,10 +139,49 @@ pub enum PluginInvokeError {
     CannotSerializePayload(serde_json::Error),
 }
 
+/// Why [`Error::ScopeDenied`] was returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ScopeDenialReason {
+    /// A `deny` entry matched.
+    Denied,
+    /// No `allow` entry matched.
+    NotAllowed,
+}
+
+fn scope_message(domain: &str, name: &str, url: Option<&str>, reason: ScopeDenialReason) -> String {
+    match (url, reason) {
+        (None, ScopeDenialReason::Denied) => {
+            format!("biometry ({domain}, {name}) denied by capability scope")
+        }
+        (None, ScopeDenialReason::NotAllowed) => format!(
+            "biometry ({domain}, {name}) not in capability allow-list — declare the (domain, name) in the capability's `allow` array"
+        ),
+        (Some(url), ScopeDenialReason::Denied) => {
+            format!("biometry ({domain}, {name}) may not be sent to {url}")
+        }
+        (Some(url), ScopeDenialReason::NotAllowed) => format!(
+            "biometry ({domain}, {name}) may not be sent to {url} — list it in the allow entry's `urls` array"
+        ),
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
     Io(#[from] std::io::Error),
+    /// The capability scope doesn't let the caller touch `(domain, name)`,
+    /// or, when `url` is set, send it to that URL.
+    #[error("{}", scope_message(domain, name, url.as_deref(), *reason))]
+    ScopeDenied {
+        domain: String,
+        name: String,
+        url: Option<String>,
+        reason: ScopeDenialReason,
+    },
+    /// A file path outside the fs plugin's scope.
+    #[error("fs {} is not allowed by the fs plugin scope", path.display())]
+    PathDenied { path: PathBuf },
     #[cfg(mobile)]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
@@ -58,11 +190,171 @@ pub enum Error {
     PluginInvoke(#[from] crate::error::PluginInvokeError),
 }
 
+impl Error {
+    /// Builds the same `InvokeRejected` error the native backends return,
+    /// so code shared by every target can report a `code` / `message` pair.
+    pub(crate) fn rejected(code: ErrorCode, message: impl Into<String>) -> Self {
+        #[cfg(desktop)]
+        use crate::error::{ErrorResponse, PluginInvokeError};
+        #[cfg(mobile)]
+        use tauri::plugin::mobile::{ErrorResponse, PluginInvokeError};
+
+        Self::PluginInvoke(PluginInvokeError::InvokeRejected(ErrorResponse {
+            code: Some(code.to_string()),
+            message: Some(message.into()),
+            data: (),
+        }))
+    }
+}
+
+impl Error {
+    /// The machine-readable error code. Rejections from the native
+    /// backends carry their own code; a rejection without one, or a failure
+    /// to reach the backend, is `InternalError`.
+    #[must_use]
+    pub fn code(&self) -> ErrorCode {
+        #[cfg(desktop)]
+        use crate::error::PluginInvokeError;
+        #[cfg(mobile)]
+        use tauri::plugin::mobile::PluginInvokeError;
+
+        match self {
+            Self::Io(_) => ErrorCode::IoError,
+            Self::ScopeDenied { .. } | Self::PathDenied { .. } => ErrorCode::ScopeDenied,
+            Self::PluginInvoke(PluginInvokeError::InvokeRejected(response)) => response
+                .code
+                .as_deref()
+                .map_or(ErrorCode::InternalError, ErrorCode::parse),
+            Self::PluginInvoke(_) => ErrorCode::InternalError,
+        }
+    }
+
+    /// The human-readable message, without the `[code] - ` prefix that
+    /// `Display` adds for backend rejections.
+    #[must_use]
+    pub fn message(&self) -> String {
+        #[cfg(desktop)]
+        use crate::error::PluginInvokeError;
+        #[cfg(mobile)]
+        use tauri::plugin::mobile::PluginInvokeError;
+
+        match self {
+            Self::PluginInvoke(PluginInvokeError::InvokeRejected(response)) => {
+                response.message.clone().unwrap_or_default()
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    fn data(&self) -> serde_json::Value {
+        match self {
+            Self::ScopeDenied {
+                domain,
+                name,
+                url,
+                reason,
+            } => serde_json::json!({
+                "domain": domain,
+                "name": name,
+                "url": url,
+                "reason": reason,
+            }),
+            Self::PathDenied { path } => serde_json::json!({ "path": path }),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+/// Serialized as `{ code, message, data }` so the frontend can branch on
+/// `code` instead of parsing the message. `data` holds error-specific
+/// details — `{ domain, name, url, reason }` for `ScopeDenied`, `{ path }`
+/// for `PathDenied` — and is `null` for everything else.
 impl Serialize for Error {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.to_string().as_ref())
+        let mut state = serializer.serialize_struct("Error", 3)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("message", &self.message())?;
+        state.serialize_field("data", &self.data())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_through_serde_and_display() {
+        for (code, wire) in [
+            (ErrorCode::UserCancel, "userCancel"),
+            (ErrorCode::DataNeedsReenrollment, "dataNeedsReenrollment"),
+            (ErrorCode::RustOnly, "rustOnly"),
+            (ErrorCode::IoError, "ioError"),
+            (ErrorCode::Unknown, "unknown"),
+        ] {
+            assert_eq!(code.to_string(), wire);
+            let json = serde_json::to_value(code).expect("serialize");
+            assert_eq!(json, serde_json::Value::String(wire.to_string()));
+            assert_eq!(
+                serde_json::from_value::<ErrorCode>(json).expect("deserialize"),
+                code
+            );
+            assert_eq!(ErrorCode::parse(wire), code);
+        }
+        assert_eq!(ErrorCode::parse("somethingNew"), ErrorCode::Unknown);
+    }
+
+    #[test]
+    fn errors_serialize_as_code_message_data() {
+        let json = |error: Error| serde_json::to_value(error).expect("serialize");
+
+        assert_eq!(
+            json(Error::rejected(ErrorCode::ItemNotFound, "Item not found")),
+            serde_json::json!({ "code": "itemNotFound", "message": "Item not found", "data": null })
+        );
+        assert_eq!(
+            json(Error::ScopeDenied {
+                domain: "com.example.app".to_string(),
+                name: "token".to_string(),
+                url: Some("https://evil.example/".to_string()),
+                reason: ScopeDenialReason::NotAllowed,
+            }),
+            serde_json::json!({
+                "code": "scopeDenied",
+                "message": "biometry (com.example.app, token) may not be sent to https://evil.example/ — list it in the allow entry's `urls` array",
+                "data": {
+                    "domain": "com.example.app",
+                    "name": "token",
+                    "url": "https://evil.example/",
+                    "reason": "notAllowed",
+                },
+            })
+        );
+        assert_eq!(
+            json(Error::ScopeDenied {
+                domain: "d".to_string(),
+                name: "n".to_string(),
+                url: None,
+                reason: ScopeDenialReason::Denied,
+            })["data"],
+            serde_json::json!({ "domain": "d", "name": "n", "url": null, "reason": "denied" })
+        );
+        assert_eq!(
+            json(Error::PathDenied {
+                path: PathBuf::from("/tmp/x"),
+            }),
+            serde_json::json!({
+                "code": "scopeDenied",
+                "message": "fs /tmp/x is not allowed by the fs plugin scope",
+                "data": { "path": "/tmp/x" },
+            })
+        );
+        let io = json(Error::Io(std::io::Error::other("disk full")));
+        assert_eq!(io["code"], "ioError");
+        assert_eq!(io["message"], "disk full");
+        assert_eq!(io["data"], serde_json::Value::Null);
     }
 }
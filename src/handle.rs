@@ -0,0 +1,308 @@
+//! Opaque handles the `get_data` command can return in place of a secret.
+//!
+//! The plaintext stays in this table and only Rust code can swap a handle
+//! for it, through `app.biometry().resolve_handle(..)`, so the secret never
+//! becomes a JS string. A handle is bound to the webview that asked for it,
+//! expires after its TTL and, unless the caller opted out, is gone after the
+//! first resolve. Entries are zeroized when they are dropped, whether by use
+//! or by expiry.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::error::ErrorCode;
+use crate::models::HandleOptions;
+use crate::sensitive::SecretString;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD as B64URL, Engine as _};
+use rand::RngExt;
+use tauri::{Manager, Runtime, Webview};
+
+const DEFAULT_TTL_MS: u64 = 60_000;
+const MAX_TTL_MS: u64 = 300_000;
+const PREFIX: &str = "bh_";
+
+struct Entry {
+    secret: SecretString,
+    webview: String,
+    expires: Instant,
+    single_use: bool,
+}
+
+#[derive(Default)]
+struct Table {
+    entries: HashMap<String, Entry>,
+    // Whether a reaper thread is running.
+    reaping: bool,
+}
+
+impl Table {
+    // Drops the entries expired by `now` and returns the earliest expiry
+    // left, if any.
+    fn purge_expired(&mut self, now: Instant) -> Option<Instant> {
+        self.entries.retain(|_, entry| entry.expires > now);
+        self.entries.values().map(|e| e.expires).min()
+    }
+}
+
+#[derive(Default)]
+struct Shared {
+    table: Mutex<Table>,
+    // Wakes the reaper when a handle is issued, since it may expire before
+    // the one the reaper is waiting for, or used, since it may have been the
+    // last one.
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, Table> {
+        self.table.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Live handles, managed as plugin state.
+#[derive(Default)]
+pub struct Store {
+    shared: Arc<Shared>,
+}
+
+impl Store {
+    /// Stores `secret` and returns a fresh handle for it, bound to the
+    /// webview labelled `webview`.
+    pub fn issue(
+        &self,
+        webview: String,
+        secret: SecretString,
+        options: &HandleOptions,
+    ) -> crate::Result<String> {
+        self.issue_at(Instant::now(), webview, secret, options)
+    }
+
+    fn issue_at(
+        &self,
+        now: Instant,
+        webview: String,
+        secret: SecretString,
+        options: &HandleOptions,
+    ) -> crate::Result<String> {
+        let ttl = ttl(options)?;
+        let mut id = [0u8; 32];
+        rand::rng().fill(&mut id);
+        let handle = format!("{PREFIX}{}", B64URL.encode(id));
+
+        let mut table = self.shared.lock();
+        table.purge_expired(now);
+        table.entries.insert(
+            handle.clone(),
+            Entry {
+                secret,
+                webview,
+                expires: now + ttl,
+                single_use: options.single_use != Some(false),
+            },
+        );
+        if table.reaping {
+            self.shared.changed.notify_one();
+        } else {
+            table.reaping = true;
+            let shared = Arc::clone(&self.shared);
+            std::thread::spawn(move || reap(&shared));
+        }
+        drop(table);
+        Ok(handle)
+    }
+
+    fn resolve(&self, webview: &str, handle: &str) -> crate::Result<SecretString> {
+        self.resolve_at(Instant::now(), webview, handle)
+    }
+
+    fn resolve_at(&self, now: Instant, webview: &str, handle: &str) -> crate::Result<SecretString> {
+        let mut table = self.shared.lock();
+        table.purge_expired(now);
+        // Unknown, expired and foreign handles are indistinguishable to the
+        // caller. A foreign webview doesn't consume the handle.
+        let entry = table
+            .entries
+            .get(handle)
+            .filter(|e| e.webview == webview)
+            .ok_or_else(invalid)?;
+        if entry.single_use {
+            let entry = table.entries.remove(handle).ok_or_else(invalid)?;
+            self.shared.changed.notify_one();
+            Ok(entry.secret)
+        } else {
+            Ok(entry.secret.clone())
+        }
+    }
+}
+
+// Drops entries as soon as they expire rather than on the next access, so
+// an unused secret doesn't outlive its TTL in memory. A single reaper runs
+// while any handle is live, sleeping until the earliest expiry.
+fn reap(shared: &Shared) {
+    let mut table = shared.lock();
+    loop {
+        let now = Instant::now();
+        let Some(next) = table.purge_expired(now) else {
+            table.reaping = false;
+            return;
+        };
+        table = shared
+            .changed
+            .wait_timeout(table, next - now)
+            .unwrap_or_else(PoisonError::into_inner)
+            .0;
+    }
+}
+
+/// The lifetime requested by `options`, checked so the `get_data` command
+/// can fail before prompting.
+pub fn ttl(options: &HandleOptions) -> crate::Result<Duration> {
+    match options.ttl.unwrap_or(DEFAULT_TTL_MS) {
+        0 => Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            "Handle ttl must be greater than zero",
+        )),
+        ms if ms > MAX_TTL_MS => Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            format!("Handle ttl must be at most {MAX_TTL_MS} ms"),
+        )),
+        ms => Ok(Duration::from_millis(ms)),
+    }
+}
+
+fn invalid() -> crate::Error {
+    crate::Error::rejected(
+        ErrorCode::InvalidHandle,
+        "Handle is unknown, expired or already used",
+    )
+}
+
+impl<R: Runtime> crate::Biometry<R> {
+    /// Returns the secret behind a handle issued by the `get_data` command
+    /// to `webview`. Single-use handles are consumed by a successful call.
+    ///
+    /// Fails with `invalidHandle` if the handle is unknown, expired, already
+    /// used or was issued to another webview.
+    // On `Biometry` so it sits with the rest of the Rust API; the handles
+    // themselves are plugin state, reached through the webview.
+    #[allow(clippy::unused_self)]
+    pub fn resolve_handle(
+        &self,
+        webview: &Webview<R>,
+        handle: &str,
+    ) -> crate::Result<SecretString> {
+        webview.state::<Store>().resolve(webview.label(), handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(store: &Store, now: Instant, ttl: u64, single_use: bool) -> String {
+        store
+            .issue_at(
+                now,
+                "main".to_string(),
+                SecretString::from("s3cret"),
+                &HandleOptions {
+                    ttl: Some(ttl),
+                    single_use: Some(single_use),
+                },
+            )
+            .expect("issue")
+    }
+
+    fn rejected(result: crate::Result<SecretString>) {
+        assert_eq!(
+            result.expect_err("resolved").code(),
+            ErrorCode::InvalidHandle
+        );
+    }
+
+    #[test]
+    fn single_use_handles_resolve_once() {
+        let store = Store::default();
+        let now = Instant::now();
+        let handle = issue(&store, now, DEFAULT_TTL_MS, true);
+        assert!(handle.starts_with(PREFIX));
+        assert_eq!(
+            &*store.resolve_at(now, "main", &handle).expect("resolve"),
+            "s3cret"
+        );
+        rejected(store.resolve_at(now, "main", &handle));
+
+        let reusable = issue(&store, now, DEFAULT_TTL_MS, false);
+        for _ in 0..2 {
+            assert_eq!(
+                &*store.resolve_at(now, "main", &reusable).expect("resolve"),
+                "s3cret"
+            );
+        }
+        rejected(store.resolve_at(now, "main", "bh_unknown"));
+    }
+
+    #[test]
+    fn other_webviews_cannot_resolve_or_consume() {
+        let store = Store::default();
+        let now = Instant::now();
+        let handle = issue(&store, now, DEFAULT_TTL_MS, true);
+        rejected(store.resolve_at(now, "other", &handle));
+        assert_eq!(
+            &*store.resolve_at(now, "main", &handle).expect("resolve"),
+            "s3cret"
+        );
+    }
+
+    #[test]
+    fn expired_handles_are_refused_and_dropped() {
+        let store = Store::default();
+        let now = Instant::now();
+        let short = issue(&store, now, 1_000, true);
+        let long = issue(&store, now, DEFAULT_TTL_MS, true);
+        let later = now + Duration::from_secs(1);
+
+        rejected(store.resolve_at(later, "main", &short));
+        assert!(!store.shared.lock().entries.contains_key(&short));
+        assert!(store.resolve_at(later, "main", &long).is_ok());
+    }
+
+    #[test]
+    fn purging_reports_the_next_expiry() {
+        let store = Store::default();
+        let now = Instant::now();
+        issue(&store, now, 1_000, true);
+        issue(&store, now, 2_000, true);
+        let mut table = store.shared.lock();
+
+        let second = now + Duration::from_secs(2);
+        assert_eq!(table.purge_expired(now), Some(now + Duration::from_secs(1)));
+        assert_eq!(
+            table.purge_expired(now + Duration::from_secs(1)),
+            Some(second)
+        );
+        // Nothing left: the reaper stops.
+        assert_eq!(table.purge_expired(second), None);
+        assert!(table.entries.is_empty());
+    }
+
+    #[test]
+    fn ttl_is_bounded() {
+        let options = |ttl| HandleOptions {
+            ttl: Some(ttl),
+            single_use: None,
+        };
+        assert_eq!(
+            ttl(&HandleOptions::default()).expect("default"),
+            Duration::from_millis(DEFAULT_TTL_MS)
+        );
+        assert!(ttl(&options(MAX_TTL_MS)).is_ok());
+        for bad in [0, MAX_TTL_MS + 1] {
+            assert_eq!(
+                ttl(&options(bad)).expect_err("ttl").code(),
+                ErrorCode::InvalidInput
+            );
+        }
+    }
+}
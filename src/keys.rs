@@ -0,0 +1,798 @@
+//! Keys for `create_key` / `sign` / `encrypt` / `decrypt` / `encrypt_file` /
+//! `decrypt_file` / `public_key` / `delete_key`. Signing keys (P-256,
+//! Ed25519) and encryption keys (ECDH-P-256) are separate algorithms, so no
+//! key is used for both.
+//!
+//! Where the backend has a non-exportable key store, the key is generated
+//! there and every use prompts through it: the Secure Enclave on macOS and
+//! iOS (P-256 and ECDH-P-256) and the Android Keystore (P-256). See each
+//! backend's `create_platform_key`.
+//!
+//! Everything else — Ed25519 everywhere, all keys on Windows, and keys on
+//! devices without such hardware — is generated in Rust. The private key is
+//! then sealed as an ordinary item in the platform store — same `domain`,
+//! name prefixed with [`RESERVED_PREFIX`] — so every `sign` goes through the
+//! same user verification as `get_data`, and no command ever returns it.
+//! Linux has no platform store; keys work there only with the unprotected
+//! software store in [`secret`].
+//!
+//! Either way the public half lives in a sidecar index next to the metadata
+//! index, so `public_key` answers without a prompt.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+use zeroize::Zeroizing;
+
+use crate::b64_field;
+use crate::error::ErrorCode;
+use crate::locks::{ItemGuard, ItemLocks};
+use crate::models::{
+    CreateKeyOptions, DecryptFileOptions, DecryptOptions, EncryptFileOptions, EncryptOptions,
+    KeyAlgorithm, PublicKeyResponse, SignOptions,
+};
+use crate::sensitive::{SecretBytes, SecretString};
+use crate::{encoding, envelope, metadata, secret, stream, BiometryExt};
+
+/// Item names starting with this are reserved for sealed private keys and
+/// rejected by the data commands (see [`secret::check_not_reserved`]).
+pub const RESERVED_PREFIX: &str = "biometry.key:";
+
+/// Key slot holding each domain's `derive_key` root; its item name is
+/// exactly [`RESERVED_PREFIX`]. Every key function rejects the empty name
+/// (see [`check_name`]), so the key API can't reach or delete the root.
+pub const ROOT_KEY_NAME: &str = "";
+
+const FILE_NAME: &str = "biometry-keys.json";
+const FILE_VERSION: u8 = 1;
+const SECRET_VERSION: u8 = 1;
+const SECRET_LEN: usize = 32;
+
+type Records = BTreeMap<(String, String), KeyRecord>;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyRecord {
+    domain: String,
+    name: String,
+    alg: KeyAlgorithm,
+    #[serde(with = "b64_field")]
+    public_key: Vec<u8>,
+    created_at: u64,
+    // Whether the private key is held by the platform's key store rather
+    // than sealed under a reserved item name.
+    #[serde(default)]
+    platform: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    v: u8,
+    keys: Vec<KeyRecord>,
+}
+
+// What gets sealed in the platform store.
+#[derive(Serialize, Deserialize)]
+struct SealedKey {
+    v: u8,
+    alg: KeyAlgorithm,
+    #[serde(with = "b64_field")]
+    d: SecretBytes,
+}
+
+// A domain's `derive_key` root, sealed the same way as a private key.
+#[cfg(not(target_os = "windows"))]
+#[derive(Serialize, Deserialize)]
+struct SealedRoot {
+    v: u8,
+    #[serde(with = "b64_field")]
+    root: SecretBytes,
+}
+
+/// Public key index, managed as plugin state.
+pub struct Store {
+    path: Option<PathBuf>,
+    keys: Mutex<Records>,
+    // Held per key across create / delete, which span a backend call and an
+    // index update.
+    writers: ItemLocks,
+}
+
+impl Store {
+    /// Loads the index from the app's local data directory, starting empty
+    /// if it is missing or unreadable.
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        let path = app
+            .path()
+            .app_local_data_dir()
+            .ok()
+            .map(|dir| dir.join(FILE_NAME));
+
+        let keys = path
+            .as_ref()
+            .and_then(|p| std::fs::read(p).ok())
+            .and_then(|bytes| match serde_json::from_slice::<IndexFile>(&bytes) {
+                Ok(file) if file.v == FILE_VERSION => Some(file.keys),
+                Ok(file) => {
+                    log::warn!("ignoring biometry key index version {}", file.v);
+                    None
+                }
+                Err(e) => {
+                    log::warn!("ignoring unreadable biometry key index: {e}");
+                    None
+                }
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|k| ((k.domain.clone(), k.name.clone()), k))
+            .collect();
+
+        Self {
+            path,
+            keys: Mutex::new(keys),
+            writers: ItemLocks::default(),
+        }
+    }
+
+    fn writer(&self, domain: &str, name: &str) -> ItemGuard<'_> {
+        self.writers.lock(domain, name)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Records> {
+        self.keys.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn get(&self, domain: &str, name: &str) -> Option<KeyRecord> {
+        self.lock()
+            .get(&(domain.to_string(), name.to_string()))
+            .cloned()
+    }
+
+    #[allow(clippy::significant_drop_tightening)]
+    fn insert(&self, record: KeyRecord) -> crate::Result<()> {
+        let mut keys = self.lock();
+        keys.insert((record.domain.clone(), record.name.clone()), record);
+        self.persist(&keys)
+    }
+
+    #[allow(clippy::significant_drop_tightening)]
+    fn remove(&self, domain: &str, name: &str) -> crate::Result<()> {
+        let mut keys = self.lock();
+        if keys
+            .remove(&(domain.to_string(), name.to_string()))
+            .is_none()
+        {
+            return Ok(());
+        }
+        self.persist(&keys)
+    }
+
+    fn persist(&self, keys: &Records) -> crate::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = IndexFile {
+            v: FILE_VERSION,
+            keys: keys.values().cloned().collect(),
+        };
+        let bytes = serde_json::to_vec(&file).map_err(std::io::Error::other)?;
+        metadata::write_atomically(path, &bytes)?;
+        Ok(())
+    }
+}
+
+/// Generates a key, seals its private half and records its public half.
+/// Fails with `conflict` if a key with this `(domain, name)` exists.
+pub fn create<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: &CreateKeyOptions,
+) -> crate::Result<PublicKeyResponse> {
+    secret::check_supported()?;
+    if options.domain.is_empty() {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            "Domain must not be empty",
+        ));
+    }
+    check_name(&options.name)?;
+    let store = app.state::<Store>();
+    let _writer = store.writer(&options.domain, &options.name);
+    if store.get(&options.domain, &options.name).is_some()
+        || secret::exists(app, &options.domain, &item_name(&options.name))?
+    {
+        return Err(crate::Error::rejected(
+            ErrorCode::Conflict,
+            "Key already exists",
+        ));
+    }
+
+    let platform_key = app.biometry().create_platform_key(
+        window.clone(),
+        &options.domain,
+        &options.name,
+        options.alg,
+    )?;
+    let platform = platform_key.is_some();
+    let public_key = if let Some(public_key) = platform_key {
+        public_key
+    } else {
+        let (d, public_key) = generate(options.alg);
+        let sealed = seal(&SealedKey {
+            v: SECRET_VERSION,
+            alg: options.alg,
+            d: SecretBytes::new(d.to_vec()),
+        })?;
+        secret::store(
+            app,
+            window,
+            &options.domain,
+            &item_name(&options.name),
+            sealed,
+        )?;
+        public_key
+    };
+
+    let record = KeyRecord {
+        domain: options.domain.clone(),
+        name: options.name.clone(),
+        alg: options.alg,
+        public_key,
+        created_at: metadata::now_ms(),
+        platform,
+    };
+    if let Err(e) = store.insert(record.clone()) {
+        // Without its index entry the key could never be used or listed.
+        let _ = remove_private_key(app, &options.domain, &options.name, platform);
+        return Err(e);
+    }
+    Ok(response(record))
+}
+
+/// Unseals the private key — prompting for user verification — and signs
+/// `payload`. P-256 signatures are raw `r || s` (64 bytes, the Web Crypto /
+/// JWS encoding); Ed25519 signatures are the usual 64 bytes.
+pub fn sign<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: SignOptions,
+) -> crate::Result<Vec<u8>> {
+    secret::check_supported()?;
+    check_name(&options.name)?;
+    let record = app
+        .state::<Store>()
+        .get(&options.domain, &options.name)
+        .ok_or_else(not_found)?;
+    if record.alg == KeyAlgorithm::EcdhP256 {
+        return Err(wrong_usage("an encryption key cannot sign"));
+    }
+    if record.platform {
+        let der = app.biometry().platform_sign(
+            window,
+            &record.domain,
+            &record.name,
+            &options.payload,
+            options.reason,
+            options.cancel_title,
+        )?;
+        return raw_p256_signature(&der);
+    }
+    let d = unseal(app, window, &record, options.reason, options.cancel_title)?;
+    sign_with(record.alg, &d, &options.payload)
+}
+
+/// Encrypts `plaintext` to an `ECDH-P-256` key. Uses only the public half,
+/// so it doesn't prompt; the plugin keeps no copy of the result.
+pub fn encrypt<R: Runtime>(app: &AppHandle<R>, options: &EncryptOptions) -> crate::Result<String> {
+    secret::check_supported()?;
+    let record = encryption_key(app, &options.domain, &options.name)?;
+    let kid = encoding::thumbprint(record.alg, &record.public_key);
+    envelope::seal(
+        &record.domain,
+        &record.name,
+        &kid,
+        &record.public_key,
+        &options.plaintext,
+    )
+}
+
+/// Unseals an `ECDH-P-256` key — prompting for user verification — and
+/// opens a ciphertext produced by [`encrypt`].
+pub fn decrypt<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: DecryptOptions,
+) -> crate::Result<SecretBytes> {
+    secret::check_supported()?;
+    let record = encryption_key(app, &options.domain, &options.name)?;
+    let kid = encoding::thumbprint(record.alg, &record.public_key);
+    envelope::open(
+        &record.domain,
+        &record.name,
+        &kid,
+        &record.public_key,
+        |epk| {
+            key_agreement(
+                app,
+                window,
+                &record,
+                epk,
+                options.reason,
+                options.cancel_title,
+            )
+        },
+        &options.ciphertext,
+    )
+}
+
+/// Encrypts a file to an `ECDH-P-256` key without prompting: a fresh data
+/// key encrypts the contents chunk by chunk, and is itself sealed to the key
+/// with the [`encrypt`] envelope in the file header.
+pub fn encrypt_file<R: Runtime>(
+    app: &AppHandle<R>,
+    options: &EncryptFileOptions,
+) -> crate::Result<()> {
+    secret::check_supported()?;
+    let record = encryption_key(app, &options.domain, &options.name)?;
+    let kid = encoding::thumbprint(record.alg, &record.public_key);
+    let dek = stream::new_dek();
+    let wrapped = envelope::seal(
+        &record.domain,
+        &record.name,
+        &kid,
+        &record.public_key,
+        dek.as_ref(),
+    )?;
+    stream::encrypt_file(&dek, wrapped, &options.input, &options.output)
+}
+
+/// Decrypts a file written by [`encrypt_file`]. Unsealing the key to unwrap
+/// the data key is the one prompt, however large the file.
+pub fn decrypt_file<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: DecryptFileOptions,
+) -> crate::Result<()> {
+    secret::check_supported()?;
+    let record = encryption_key(app, &options.domain, &options.name)?;
+    let kid = encoding::thumbprint(record.alg, &record.public_key);
+    stream::decrypt_file(&options.input, &options.output, |wrapped| {
+        envelope::open(
+            &record.domain,
+            &record.name,
+            &kid,
+            &record.public_key,
+            |epk| {
+                key_agreement(
+                    app,
+                    window,
+                    &record,
+                    epk,
+                    options.reason,
+                    options.cancel_title,
+                )
+            },
+            wrapped,
+        )
+    })
+}
+
+// The file API on `Biometry`, for files the app picks itself. Unlike the
+// commands, the paths aren't checked against the fs scope.
+impl<R: Runtime> crate::Biometry<R> {
+    /// Encrypts `options.input` to `options.output` with the `ECDH-P-256`
+    /// key `(options.domain, options.name)`, streaming it. Does not prompt.
+    pub fn encrypt_file(&self, options: &EncryptFileOptions) -> crate::Result<()> {
+        encrypt_file(self.app(), options)
+    }
+
+    /// Decrypts a file written by [`Self::encrypt_file`], prompting once.
+    /// Fails with `decryptionFailed` if the file was modified, truncated or
+    /// reordered, and leaves nothing at `options.output`.
+    pub fn decrypt_file(
+        &self,
+        window: WebviewWindow<R>,
+        options: DecryptFileOptions,
+    ) -> crate::Result<()> {
+        decrypt_file(self.app(), window, options)
+    }
+}
+
+/// Returns `domain`'s `derive_key` root secret, prompting for user
+/// verification. The root is generated and sealed on first use and never
+/// leaves Rust.
+#[cfg(not(target_os = "windows"))]
+pub fn root_secret<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    domain: &str,
+    reason: String,
+    cancel_title: Option<String>,
+) -> crate::Result<Zeroizing<[u8; SECRET_LEN]>> {
+    {
+        let store = app.state::<Store>();
+        let _writer = store.writer(domain, ROOT_KEY_NAME);
+        if !secret::exists(app, domain, &item_name(ROOT_KEY_NAME))? {
+            let mut root = Zeroizing::new([0u8; SECRET_LEN]);
+            rand::rng().fill(root.as_mut());
+            let sealed = seal(&SealedRoot {
+                v: SECRET_VERSION,
+                root: SecretBytes::new(root.to_vec()),
+            })?;
+            secret::store(
+                app,
+                window.clone(),
+                domain,
+                &item_name(ROOT_KEY_NAME),
+                sealed,
+            )?;
+        }
+    }
+
+    let sealed = secret::load(
+        app,
+        window,
+        domain,
+        &item_name(ROOT_KEY_NAME),
+        reason,
+        cancel_title,
+    )?;
+    let sealed: SealedRoot = serde_json::from_str(&sealed).map_err(|e| {
+        crate::Error::rejected(ErrorCode::InternalError, format!("sealed root: {e}"))
+    })?;
+    if sealed.v != SECRET_VERSION {
+        return Err(crate::Error::rejected(
+            ErrorCode::InternalError,
+            "Sealed root has an unknown version",
+        ));
+    }
+    <[u8; SECRET_LEN]>::try_from(&*sealed.root)
+        .map(Zeroizing::new)
+        .map_err(|_| {
+            crate::Error::rejected(ErrorCode::InternalError, "Sealed root has wrong length")
+        })
+}
+
+pub fn public_key<R: Runtime>(
+    app: &AppHandle<R>,
+    domain: &str,
+    name: &str,
+) -> crate::Result<PublicKeyResponse> {
+    secret::check_supported()?;
+    check_name(name)?;
+    app.state::<Store>()
+        .get(domain, name)
+        .map(response)
+        .ok_or_else(not_found)
+}
+
+/// Returns the public key of the `WebAuthn` credential that protects
+/// `domain`'s items on Windows, as a P-256 key named `""`. Fails with
+/// `itemNotFound` before the domain's first item is stored.
+#[cfg(target_os = "windows")]
+pub fn credential_public_key<R: Runtime>(
+    app: &AppHandle<R>,
+    domain: &str,
+) -> crate::Result<PublicKeyResponse> {
+    let public_key = app.biometry().credential_public_key(domain)?;
+    Ok(response(KeyRecord {
+        domain: domain.to_string(),
+        name: String::new(),
+        alg: KeyAlgorithm::P256,
+        public_key,
+        created_at: 0,
+        platform: true,
+    }))
+}
+
+/// Only Windows protects items with a `WebAuthn` credential.
+#[cfg(not(target_os = "windows"))]
+pub fn credential_public_key<R: Runtime>(
+    _app: &AppHandle<R>,
+    _domain: &str,
+) -> crate::Result<PublicKeyResponse> {
+    Err(crate::Error::rejected(
+        ErrorCode::NotSupported,
+        "Only Windows protects items with a WebAuthn credential",
+    ))
+}
+
+/// Deletes the private key and the index entry. Succeeds if the key
+/// doesn't exist, like `remove_data`.
+pub fn delete<R: Runtime>(app: &AppHandle<R>, domain: &str, name: &str) -> crate::Result<()> {
+    secret::check_supported()?;
+    check_name(name)?;
+    let store = app.state::<Store>();
+    let _writer = store.writer(domain, name);
+    let platform = store.get(domain, name).is_some_and(|r| r.platform);
+    remove_private_key(app, domain, name, platform)?;
+    store.remove(domain, name)
+}
+
+fn remove_private_key<R: Runtime>(
+    app: &AppHandle<R>,
+    domain: &str,
+    name: &str,
+    platform: bool,
+) -> crate::Result<()> {
+    if platform {
+        app.biometry().delete_platform_key(domain, name)
+    } else {
+        secret::remove(app, domain, &item_name(name))
+    }
+}
+
+fn sign_with(alg: KeyAlgorithm, d: &[u8; SECRET_LEN], payload: &[u8]) -> crate::Result<Vec<u8>> {
+    match alg {
+        KeyAlgorithm::P256 => {
+            use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+            let key = SigningKey::from_bytes(&(*d).into()).map_err(|e| {
+                crate::Error::rejected(ErrorCode::InternalError, format!("p-256 key: {e}"))
+            })?;
+            let signature: Signature = key.sign(payload);
+            Ok(signature.to_bytes().to_vec())
+        }
+        KeyAlgorithm::Ed25519 => {
+            use ed25519_dalek::{Signer, SigningKey};
+            let key = SigningKey::from_bytes(d);
+            Ok(key.sign(payload).to_bytes().to_vec())
+        }
+        KeyAlgorithm::EcdhP256 => Err(wrong_usage("an encryption key cannot sign")),
+    }
+}
+
+// Platform key stores return X9.62 DER signatures; `sign` returns raw
+// `r || s` for every key.
+fn raw_p256_signature(der: &[u8]) -> crate::Result<Vec<u8>> {
+    p256::ecdsa::Signature::from_der(der)
+        .map(|signature| signature.to_bytes().to_vec())
+        .map_err(|e| {
+            crate::Error::rejected(ErrorCode::InternalError, format!("p-256 signature: {e}"))
+        })
+}
+
+// ECDH between an `ECDH-P-256` key and the envelope's ephemeral key `epk`
+// (uncompressed SEC1): the step of `decrypt` that prompts.
+fn key_agreement<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    record: &KeyRecord,
+    epk: &[u8],
+    reason: String,
+    cancel_title: Option<String>,
+) -> crate::Result<Zeroizing<[u8; SECRET_LEN]>> {
+    if record.platform {
+        let shared = app.biometry().platform_key_agreement(
+            window,
+            &record.domain,
+            &record.name,
+            epk,
+            reason,
+            cancel_title,
+        )?;
+        return <[u8; SECRET_LEN]>::try_from(&*shared)
+            .map(Zeroizing::new)
+            .map_err(|_| {
+                crate::Error::rejected(ErrorCode::InternalError, "Shared secret has wrong length")
+            });
+    }
+    let d = unseal(app, window, record, reason, cancel_title)?;
+    let secret = p256::SecretKey::from_bytes(&(*d).into())
+        .map_err(|e| crate::Error::rejected(ErrorCode::InternalError, format!("p-256 key: {e}")))?;
+    let epk = p256::PublicKey::from_sec1_bytes(epk).map_err(|e| {
+        crate::Error::rejected(ErrorCode::InvalidInput, format!("ephemeral key: {e}"))
+    })?;
+    let shared = p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), epk.as_affine());
+    let mut out = Zeroizing::new([0u8; SECRET_LEN]);
+    out.copy_from_slice(shared.raw_secret_bytes());
+    Ok(out)
+}
+
+// Private scalars are drawn straight from the OS RNG. Out-of-range P-256
+// scalars (probability ~2^-32) are redrawn.
+fn generate(alg: KeyAlgorithm) -> (Zeroizing<[u8; SECRET_LEN]>, Vec<u8>) {
+    loop {
+        let mut d = Zeroizing::new([0u8; SECRET_LEN]);
+        rand::rng().fill(d.as_mut());
+        match alg {
+            KeyAlgorithm::P256 | KeyAlgorithm::EcdhP256 => {
+                if let Ok(key) = p256::ecdsa::SigningKey::from_bytes(&(*d).into()) {
+                    let point = key.verifying_key().to_encoded_point(false);
+                    return (d, point.as_bytes().to_vec());
+                }
+            }
+            KeyAlgorithm::Ed25519 => {
+                let key = ed25519_dalek::SigningKey::from_bytes(&d);
+                return (d, key.verifying_key().to_bytes().to_vec());
+            }
+        }
+    }
+}
+
+fn encryption_key<R: Runtime>(
+    app: &AppHandle<R>,
+    domain: &str,
+    name: &str,
+) -> crate::Result<KeyRecord> {
+    check_name(name)?;
+    let record = app
+        .state::<Store>()
+        .get(domain, name)
+        .ok_or_else(not_found)?;
+    if record.alg != KeyAlgorithm::EcdhP256 {
+        return Err(wrong_usage("only ECDH-P-256 keys can encrypt"));
+    }
+    Ok(record)
+}
+
+// Reads the sealed private scalar back from the platform store. This is the
+// step that prompts.
+fn unseal<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    record: &KeyRecord,
+    reason: String,
+    cancel_title: Option<String>,
+) -> crate::Result<Zeroizing<[u8; SECRET_LEN]>> {
+    let sealed = secret::load(
+        app,
+        window,
+        &record.domain,
+        &item_name(&record.name),
+        reason,
+        cancel_title,
+    )?;
+    let sealed: SealedKey = serde_json::from_str(&sealed).map_err(|e| {
+        crate::Error::rejected(ErrorCode::InternalError, format!("sealed key: {e}"))
+    })?;
+    if sealed.v != SECRET_VERSION || sealed.alg != record.alg {
+        return Err(crate::Error::rejected(
+            ErrorCode::InternalError,
+            "Sealed key does not match its index entry",
+        ));
+    }
+    <[u8; SECRET_LEN]>::try_from(&*sealed.d)
+        .map(Zeroizing::new)
+        .map_err(|_| {
+            crate::Error::rejected(ErrorCode::InternalError, "Sealed key has wrong length")
+        })
+}
+
+// Serializes a private key or root for the platform store.
+fn seal(value: &impl Serialize) -> crate::Result<SecretString> {
+    serde_json::to_string(value)
+        .map(SecretString::new)
+        .map_err(|e| std::io::Error::other(e).into())
+}
+
+fn response(record: KeyRecord) -> PublicKeyResponse {
+    let spki = encoding::spki(record.alg, &record.public_key);
+    PublicKeyResponse {
+        pem: encoding::pem(&spki),
+        jwk: encoding::jwk(record.alg, &record.public_key),
+        cose_key: encoding::cose_key(record.alg, &record.public_key),
+        thumbprint: encoding::thumbprint(record.alg, &record.public_key),
+        platform: record.platform,
+        spki,
+        domain: record.domain,
+        name: record.name,
+        alg: record.alg,
+        public_key: record.public_key,
+    }
+}
+
+// The empty name is the `derive_key` root's slot ([`ROOT_KEY_NAME`]), which
+// no key function may address.
+fn check_name(name: &str) -> crate::Result<()> {
+    if name.is_empty() {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            "Key name must not be empty",
+        ));
+    }
+    Ok(())
+}
+
+// The reserved item a key's private half is sealed under.
+fn item_name(name: &str) -> String {
+    format!("{RESERVED_PREFIX}{name}")
+}
+
+fn not_found() -> crate::Error {
+    crate::Error::rejected(ErrorCode::ItemNotFound, "Key not found")
+}
+
+fn wrong_usage(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The software store stands in for the platform store on Linux.
+    #[cfg(all(desktop, not(target_os = "windows"), not(target_os = "macos")))]
+    #[test]
+    fn delete_key_cannot_reach_the_derive_root() {
+        use tauri::{test::mock_app, WebviewUrl, WebviewWindowBuilder};
+
+        let app = mock_app();
+        app.manage(Store {
+            path: None,
+            keys: Mutex::default(),
+            writers: ItemLocks::default(),
+        });
+        app.manage(secret::Software::in_memory());
+        let window = WebviewWindowBuilder::new(&app, "main", WebviewUrl::default())
+            .build()
+            .expect("window");
+        let root = item_name(ROOT_KEY_NAME);
+        secret::store(
+            app.handle(),
+            window,
+            "com.example.app",
+            &root,
+            SecretString::new("root".to_string()),
+        )
+        .expect("store root");
+
+        let err = delete(app.handle(), "com.example.app", "").expect_err("deleted");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+        assert!(secret::exists(app.handle(), "com.example.app", &root).expect("exists"));
+    }
+
+    #[test]
+    fn p256_signatures_verify_against_the_public_key() {
+        use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+
+        let (d, public_key) = generate(KeyAlgorithm::P256);
+        let signature = sign_with(KeyAlgorithm::P256, &d, b"challenge").expect("sign");
+        assert_eq!(signature.len(), 64);
+
+        let key = VerifyingKey::from_sec1_bytes(&public_key).expect("public key");
+        let signature = Signature::from_slice(&signature).expect("raw signature");
+        key.verify(b"challenge", &signature).expect("verify");
+        assert!(key.verify(b"other challenge", &signature).is_err());
+    }
+
+    #[test]
+    fn ed25519_signatures_verify_against_the_public_key() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let (d, public_key) = generate(KeyAlgorithm::Ed25519);
+        let signature = sign_with(KeyAlgorithm::Ed25519, &d, b"challenge").expect("sign");
+
+        let public_key = <[u8; 32]>::try_from(public_key.as_slice()).expect("key length");
+        let key = VerifyingKey::from_bytes(&public_key).expect("public key");
+        let signature = Signature::from_slice(&signature).expect("signature");
+        key.verify(b"challenge", &signature).expect("verify");
+        assert!(key.verify(b"other challenge", &signature).is_err());
+    }
+
+    #[test]
+    fn encryption_keys_cannot_sign() {
+        let (d, _) = generate(KeyAlgorithm::EcdhP256);
+        let err = sign_with(KeyAlgorithm::EcdhP256, &d, b"challenge").expect_err("signed");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+    }
+
+    #[test]
+    fn platform_der_signatures_become_raw() {
+        use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let (d, _) = generate(KeyAlgorithm::P256);
+        let key = SigningKey::from_bytes(&(*d).into()).expect("key");
+        let signature: Signature = key.sign(b"challenge");
+
+        let raw = raw_p256_signature(signature.to_der().as_bytes()).expect("der");
+        assert_eq!(raw, signature.to_bytes().to_vec());
+        assert_eq!(
+            raw_p256_signature(&raw).expect_err("raw is not der").code(),
+            ErrorCode::InternalError
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use tauri::{
     plugin::{Builder, TauriPlugin},
-    Manager, Runtime,
+    Manager, RunEvent, Runtime, WindowEvent,
 };
 
 pub use models::*;
@@ -14,13 +14,33 @@ mod mobile;
 #[cfg(target_os = "windows")]
 mod windows;
 
+mod archive;
+mod b64_field;
 mod commands;
+mod derive;
+mod encoding;
+mod envelope;
 mod error;
+mod handle;
+#[cfg(feature = "http")]
+mod http;
+mod keys;
+mod locks;
+mod metadata;
 mod models;
+mod otp;
+mod payload;
+mod rust_only;
 mod scope;
+mod secret;
+mod sensitive;
+mod status;
+mod storage;
+mod stream;
 
-pub use error::{Error, Result};
-pub use scope::Entry as ScopeEntry;
+pub use error::{Error, ErrorCode, Result, ScopeDenialReason};
+pub use scope::{Entry as ScopeEntry, Op as ScopeOp};
+pub use sensitive::{SecretBytes, SecretString};
 
 #[cfg(all(desktop, not(target_os = "windows"), not(target_os = "macos")))]
 use desktop::Biometry;
@@ -53,6 +73,25 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::get_data,
             commands::set_data,
             commands::remove_data,
+            commands::rename_data,
+            commands::get_metadata,
+            commands::purge_expired,
+            commands::export_domain,
+            commands::import_domain,
+            commands::fetch_with_secret,
+            commands::create_key,
+            commands::sign,
+            commands::encrypt,
+            commands::decrypt,
+            commands::encrypt_file,
+            commands::decrypt_file,
+            commands::derive_key,
+            commands::import_otp,
+            commands::generate_otp,
+            commands::delete_otp,
+            commands::public_key,
+            commands::credential_public_key,
+            commands::delete_key,
         ])
         .setup(|app, api| {
             #[cfg(mobile)]
@@ -64,7 +103,28 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             #[cfg(target_os = "macos")]
             let biometry = macos::init(app, api)?;
             app.manage(biometry);
+            app.manage(metadata::Store::load(app));
+            app.manage(keys::Store::load(app));
+            app.manage(otp::Store::load(app));
+            app.manage(handle::Store::default());
+            app.manage(status::Cache::default());
+            #[cfg(all(
+                desktop,
+                not(target_os = "windows"),
+                not(target_os = "macos"),
+                any(test, feature = "insecure-software-store")
+            ))]
+            app.manage(secret::Software::load(app));
             Ok(())
         })
+        .on_event(|app, event| {
+            if let RunEvent::WindowEvent {
+                event: WindowEvent::Focused(true),
+                ..
+            } = event
+            {
+                app.state::<status::Cache>().invalidate();
+            }
+        })
         .build()
 }
@@ -0,0 +1,101 @@
+//! Per-item write locks for the stores that pair a backend call with an
+//! index update. Such a call can sit on a biometric prompt for as long as
+//! the user takes; it must hold up other writes to the same item, never
+//! writes to every other item.
+//!
+//! Waiting blocks the thread, so the commands only reach these locks from
+//! `spawn_blocking` tasks, never on an async runtime worker.
+
+use std::collections::BTreeSet;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+type Key = (String, String);
+
+/// The `(domain, name)` pairs currently held.
+#[derive(Default)]
+pub struct ItemLocks {
+    held: Mutex<BTreeSet<Key>>,
+    released: Condvar,
+}
+
+/// Holds one or more items until dropped.
+pub struct ItemGuard<'a> {
+    locks: &'a ItemLocks,
+    keys: Vec<Key>,
+}
+
+impl ItemLocks {
+    /// Blocks until `(domain, name)` is free, then holds it.
+    pub fn lock(&self, domain: &str, name: &str) -> ItemGuard<'_> {
+        self.lock_all(&[(domain, name)])
+    }
+
+    /// Blocks until every item is free, then holds them all. Nothing is held
+    /// while waiting, so callers locking overlapping sets can't deadlock.
+    pub fn lock_all(&self, items: &[(&str, &str)]) -> ItemGuard<'_> {
+        let keys: Vec<Key> = items
+            .iter()
+            .map(|(domain, name)| ((*domain).to_string(), (*name).to_string()))
+            .collect();
+        let mut held = self.held();
+        while keys.iter().any(|key| held.contains(key)) {
+            held = self
+                .released
+                .wait(held)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        held.extend(keys.iter().cloned());
+        drop(held);
+        ItemGuard { locks: self, keys }
+    }
+
+    fn held(&self) -> MutexGuard<'_, BTreeSet<Key>> {
+        self.held.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Drop for ItemGuard<'_> {
+    fn drop(&mut self) {
+        let mut held = self.locks.held();
+        for key in &self.keys {
+            held.remove(key);
+        }
+        drop(held);
+        self.locks.released.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn other_items_are_not_blocked() {
+        let locks = ItemLocks::default();
+        let _a = locks.lock("d", "a");
+        let _b = locks.lock("d", "b");
+        let _other_domain = locks.lock("e", "a");
+    }
+
+    #[test]
+    fn same_item_waits_for_release() {
+        let locks = ItemLocks::default();
+        let (tx, rx) = mpsc::channel();
+        std::thread::scope(|scope| {
+            let guard = locks.lock("d", "a");
+            scope.spawn(|| {
+                let _guard = locks.lock_all(&[("d", "b"), ("d", "a")]);
+                tx.send(()).expect("receiver alive");
+            });
+            assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+            drop(guard);
+            rx.recv_timeout(Duration::from_secs(5))
+                .expect("released lock is taken");
+        });
+        // Both were released again.
+        let _guard = locks.lock_all(&[("d", "a"), ("d", "b")]);
+    }
+}
@@ -1,25 +1,32 @@
 use objc2_core_foundation::{
     kCFCopyStringDictionaryKeyCallBacks, kCFTypeDictionaryValueCallBacks, CFBoolean, CFData,
-    CFDictionary, CFIndex, CFRetained, CFString, CFType,
+    CFDictionary, CFError, CFIndex, CFNumber, CFRetained, CFString, CFType,
 };
 use objc2_local_authentication::{LABiometryType, LAContext, LAError, LAPolicy};
 use objc2_security::{
     errSecDuplicateItem, errSecInteractionNotAllowed, errSecItemNotFound, errSecSuccess,
     errSecUserCanceled, kSecAttrAccessControl, kSecAttrAccessibleWhenUnlockedThisDeviceOnly,
-    kSecAttrAccount, kSecAttrService, kSecClass, kSecClassGenericPassword, kSecMatchLimit,
-    kSecMatchLimitOne, kSecReturnData, kSecUseAuthenticationContext, kSecUseDataProtectionKeychain,
-    kSecValueData, SecAccessControl, SecAccessControlCreateFlags, SecItemAdd, SecItemCopyMatching,
-    SecItemDelete, SecItemUpdate,
+    kSecAttrAccount, kSecAttrApplicationTag, kSecAttrIsPermanent, kSecAttrKeyClass,
+    kSecAttrKeyClassPrivate, kSecAttrKeyClassPublic, kSecAttrKeySizeInBits, kSecAttrKeyType,
+    kSecAttrKeyTypeECSECPrimeRandom, kSecAttrService, kSecAttrTokenID,
+    kSecAttrTokenIDSecureEnclave, kSecClass, kSecClassGenericPassword, kSecClassKey,
+    kSecKeyAlgorithmECDHKeyExchangeStandard, kSecKeyAlgorithmECDSASignatureMessageX962SHA256,
+    kSecMatchLimit, kSecMatchLimitOne, kSecPrivateKeyAttrs, kSecReturnData, kSecReturnRef,
+    kSecUseAuthenticationContext, kSecUseDataProtectionKeychain, kSecValueData, SecAccessControl,
+    SecAccessControlCreateFlags, SecItemAdd, SecItemCopyMatching, SecItemDelete, SecItemUpdate,
+    SecKey,
 };
 use serde::de::DeserializeOwned;
 use std::ffi::c_void;
 use tauri::{plugin::PluginApi, AppHandle, Runtime, WebviewWindow};
 
-use crate::error::{ErrorResponse, PluginInvokeError};
+use crate::error::{ErrorCode, ErrorResponse, PluginInvokeError};
 use crate::models::{
-    AuthOptions, BiometryType, DataOptions, DataResponse, GetDataOptions, RemoveDataOptions,
-    SetDataOptions, Status,
+    AuthOptions, BiometryStrength, BiometryType, Capabilities, DataOptions, DataResponse,
+    GetDataOptions, KeyAlgorithm, RemoveDataOptions, RenameDataOptions, SetDataOptions, Status,
 };
+use crate::sensitive::SecretBytes;
+use crate::{keys, payload};
 
 // Signature must match the cross-platform plugin contract — return type is
 // fixed even though macOS init can't fail.
@@ -31,7 +38,7 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
     Ok(Biometry(app.clone()))
 }
 
-fn reject(code: &str, message: &str) -> crate::Error {
+fn reject(code: ErrorCode, message: &str) -> crate::Error {
     crate::Error::PluginInvoke(PluginInvokeError::InvokeRejected(ErrorResponse {
         code: Some(code.to_string()),
         message: Some(message.to_string()),
@@ -40,24 +47,28 @@ fn reject(code: &str, message: &str) -> crate::Error {
 }
 
 fn cf_len(n: usize) -> crate::Result<CFIndex> {
-    CFIndex::try_from(n)
-        .map_err(|_| reject("internalError", "CF array length does not fit in CFIndex"))
+    CFIndex::try_from(n).map_err(|_| {
+        reject(
+            ErrorCode::InternalError,
+            "CF array length does not fit in CFIndex",
+        )
+    })
 }
 
-const fn la_error_to_string(error: LAError) -> &'static str {
+const fn la_error_code(error: LAError) -> ErrorCode {
     match error {
-        LAError::AppCancel => "appCancel",
-        LAError::AuthenticationFailed => "authenticationFailed",
-        LAError::InvalidContext => "invalidContext",
-        LAError::NotInteractive => "notInteractive",
-        LAError::PasscodeNotSet => "passcodeNotSet",
-        LAError::SystemCancel => "systemCancel",
-        LAError::UserCancel => "userCancel",
-        LAError::UserFallback => "userFallback",
-        LAError::BiometryLockout => "biometryLockout",
-        LAError::BiometryNotAvailable => "biometryNotAvailable",
-        LAError::BiometryNotEnrolled => "biometryNotEnrolled",
-        _ => "unknown",
+        LAError::AppCancel => ErrorCode::AppCancel,
+        LAError::AuthenticationFailed => ErrorCode::AuthenticationFailed,
+        LAError::InvalidContext => ErrorCode::InvalidContext,
+        LAError::NotInteractive => ErrorCode::NotInteractive,
+        LAError::PasscodeNotSet => ErrorCode::PasscodeNotSet,
+        LAError::SystemCancel => ErrorCode::SystemCancel,
+        LAError::UserCancel => ErrorCode::UserCancel,
+        LAError::UserFallback => ErrorCode::UserFallback,
+        LAError::BiometryLockout => ErrorCode::BiometryLockout,
+        LAError::BiometryNotAvailable => ErrorCode::BiometryNotAvailable,
+        LAError::BiometryNotEnrolled => ErrorCode::BiometryNotEnrolled,
+        _ => ErrorCode::Unknown,
     }
 }
 
@@ -65,6 +76,10 @@ const fn la_error_to_string(error: LAError) -> &'static str {
 pub struct Biometry<R: Runtime>(AppHandle<R>);
 
 impl<R: Runtime> Biometry<R> {
+    pub(crate) const fn app(&self) -> &AppHandle<R> {
+        &self.0
+    }
+
     // macOS uses global LAContext/Keychain APIs, so methods don't need
     // per-instance state. Signatures match the cross-platform shape.
     #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
@@ -84,7 +99,7 @@ impl<R: Runtime> Biometry<R> {
             let code = LAError(ns_error.code());
             (
                 Some(description.to_string()),
-                Some(la_error_to_string(code).to_string()),
+                Some(la_error_code(code).to_string()),
             )
         } else {
             (None, None)
@@ -94,14 +109,42 @@ impl<R: Runtime> Biometry<R> {
         let mapped_biometry_type = match biometry_type {
             LABiometryType::TouchID => BiometryType::TouchID,
             LABiometryType::FaceID => BiometryType::FaceID,
+            LABiometryType::OpticID => BiometryType::OpticID,
             _ => BiometryType::None,
         };
+        let modalities = if is_available && mapped_biometry_type != BiometryType::None {
+            vec![mapped_biometry_type]
+        } else {
+            Vec::new()
+        };
+
+        // Items are stored with `userPresence`, which the login password
+        // satisfies as well, so storage only needs the owner policy.
+        let device_credential =
+            unsafe { context.canEvaluatePolicy_error(LAPolicy::DeviceOwnerAuthentication) }.is_ok();
+        let os = objc2_foundation::NSProcessInfo::processInfo().operatingSystemVersion();
 
         Ok(Status {
             is_available,
             biometry_type: mapped_biometry_type,
             error: error_reason,
             error_code,
+            modalities,
+            capabilities: Capabilities {
+                storage: device_credential,
+                device_credential,
+                keys: device_credential,
+                strength: if is_available {
+                    BiometryStrength::Strong
+                } else {
+                    BiometryStrength::None
+                },
+                backend: "keychain".to_string(),
+                backend_version: Some(format!(
+                    "{}.{}.{}",
+                    os.majorVersion, os.minorVersion, os.patchVersion
+                )),
+            },
         })
     }
 
@@ -127,7 +170,7 @@ impl<R: Runtime> Biometry<R> {
                 let ns_error = &*error;
                 let description = ns_error.localizedDescription();
                 let code = LAError(ns_error.code());
-                let error_code = la_error_to_string(code);
+                let error_code = la_error_code(code);
 
                 return Err(reject(error_code, &description.to_string()));
             }
@@ -179,11 +222,14 @@ impl<R: Runtime> Biometry<R> {
                             let error = &*error_ptr;
                             let description = error.localizedDescription().to_string();
                             let code = LAError(error.code());
-                            let error_code = la_error_to_string(code);
+                            let error_code = la_error_code(code);
 
                             let _ = tx.send(Err(reject(error_code, &description)));
                         } else {
-                            let _ = tx.send(Err(reject("authenticationFailed", "Unknown error")));
+                            let _ = tx.send(Err(reject(
+                                ErrorCode::AuthenticationFailed,
+                                "Unknown error",
+                            )));
                         }
                     },
                 ),
@@ -193,14 +239,14 @@ impl<R: Runtime> Biometry<R> {
         // Wait for authentication result
         rx.recv().unwrap_or_else(|_| {
             Err(reject(
-                "authenticationFailed",
+                ErrorCode::AuthenticationFailed,
                 "Failed to receive authentication result",
             ))
         })
     }
 
     #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
-    pub fn has_data(&self, options: DataOptions) -> crate::Result<bool> {
+    pub(crate) fn backend_has_data(&self, options: DataOptions) -> crate::Result<bool> {
         unsafe {
             let account_cf: CFRetained<CFString> = CFString::from_str(&options.name);
             let service_cf: CFRetained<CFString> = CFString::from_str(&options.domain);
@@ -250,7 +296,12 @@ impl<R: Runtime> Biometry<R> {
                 std::ptr::addr_of!(kCFCopyStringDictionaryKeyCallBacks),
                 std::ptr::addr_of!(kCFTypeDictionaryValueCallBacks),
             )
-            .ok_or_else(|| reject("internalError", "Failed to create CFDictionary for query"))?;
+            .ok_or_else(|| {
+                reject(
+                    ErrorCode::InternalError,
+                    "Failed to create CFDictionary for query",
+                )
+            })?;
 
             let status = SecItemCopyMatching(&query, std::ptr::null_mut());
 
@@ -260,7 +311,7 @@ impl<R: Runtime> Biometry<R> {
                 Ok(false)
             } else {
                 Err(reject(
-                    "keychainError",
+                    ErrorCode::KeychainError,
                     &format!("SecItemCopyMatching failed with status: {status}"),
                 ))
             }
@@ -268,7 +319,7 @@ impl<R: Runtime> Biometry<R> {
     }
 
     #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
-    pub fn get_data(
+    pub(crate) fn backend_get_data(
         &self,
         _window: WebviewWindow<R>,
         options: GetDataOptions,
@@ -313,7 +364,12 @@ impl<R: Runtime> Biometry<R> {
                 std::ptr::addr_of!(kCFCopyStringDictionaryKeyCallBacks),
                 std::ptr::addr_of!(kCFTypeDictionaryValueCallBacks),
             )
-            .ok_or_else(|| reject("internalError", "Failed to create CFDictionary for query"))?;
+            .ok_or_else(|| {
+                reject(
+                    ErrorCode::InternalError,
+                    "Failed to create CFDictionary for query",
+                )
+            })?;
 
             let mut out: *const CFType = std::ptr::null();
             let status = SecItemCopyMatching(&query, &mut out);
@@ -321,34 +377,34 @@ impl<R: Runtime> Biometry<R> {
             if status == errSecSuccess {
                 if out.is_null() {
                     Err(reject(
-                        "dataError",
+                        ErrorCode::InternalError,
                         "SecItemCopyMatching returned null data",
                     ))
                 } else {
                     let cf_data: &CFData = &*out.cast::<CFData>();
                     let bytes = cf_data.byte_ptr();
                     let data = std::slice::from_raw_parts(bytes, cf_data.len() as usize);
-                    Ok(DataResponse {
-                        domain: options.domain,
-                        name: options.name,
-                        data: String::from_utf8_lossy(data).to_string(),
-                    })
+                    Ok(payload::response(
+                        options.domain,
+                        options.name,
+                        String::from_utf8_lossy(data).into_owned().into(),
+                    ))
                 }
             } else if status == errSecItemNotFound {
                 Err(reject(
-                    "itemNotFound",
+                    ErrorCode::ItemNotFound,
                     &format!("Error retrieving item from keychain: {status}"),
                 ))
             } else if status == errSecUserCanceled {
-                Err(reject("userCancel", "User canceled"))
+                Err(reject(ErrorCode::UserCancel, "User canceled"))
             } else if status == errSecInteractionNotAllowed {
                 Err(reject(
-                    "authenticationRequired",
+                    ErrorCode::AuthenticationRequired,
                     "Authentication required but UI interaction is not allowed",
                 ))
             } else {
                 Err(reject(
-                    "keychainError",
+                    ErrorCode::KeychainError,
                     &format!("Error retrieving item from keychain: {status}"),
                 ))
             }
@@ -360,7 +416,7 @@ impl<R: Runtime> Biometry<R> {
         clippy::needless_pass_by_value,
         clippy::too_many_lines
     )]
-    pub fn set_data(
+    pub(crate) fn backend_set_data(
         &self,
         _window: WebviewWindow<R>,
         options: SetDataOptions,
@@ -368,7 +424,8 @@ impl<R: Runtime> Biometry<R> {
         unsafe {
             let cf_account: CFRetained<CFString> = CFString::from_str(&options.name);
             let cf_service: CFRetained<CFString> = CFString::from_str(&options.domain);
-            let cf_value: CFRetained<CFData> = CFData::from_bytes(options.data.as_bytes());
+            let cf_value: CFRetained<CFData> =
+                CFData::from_bytes(payload::stored(&options).as_bytes());
 
             // Create SecAccessControl(userPresence)
             let ac_ref = SecAccessControl::with_flags(
@@ -377,7 +434,12 @@ impl<R: Runtime> Biometry<R> {
                 SecAccessControlCreateFlags::UserPresence,
                 std::ptr::null_mut(),
             )
-            .ok_or_else(|| reject("internalError", "Failed to create SecAccessControl"))?;
+            .ok_or_else(|| {
+                reject(
+                    ErrorCode::InternalError,
+                    "Failed to create SecAccessControl",
+                )
+            })?;
 
             // Attributes for SecItemAdd. The `SecAccessControl` built
             // above already encodes the accessibility class — passing
@@ -413,12 +475,18 @@ impl<R: Runtime> Biometry<R> {
             )
             .ok_or_else(|| {
                 reject(
-                    "internalError",
+                    ErrorCode::InternalError,
                     "Failed to create CFDictionary for add_dict",
                 )
             })?;
 
             let mut status = SecItemAdd(&add_dict, std::ptr::null_mut());
+            // Create-only writes must not fall through to the update below:
+            // the Keychain's own duplicate check is what makes them atomic
+            // across processes.
+            if status == errSecDuplicateItem && options.if_absent == Some(true) {
+                return Err(reject(ErrorCode::Conflict, "Item already exists"));
+            }
             if status == errSecDuplicateItem {
                 // Query dict (class + account + service). Same backend
                 // opt-in as the add — otherwise SecItemUpdate looks at
@@ -447,7 +515,7 @@ impl<R: Runtime> Biometry<R> {
                 )
                 .ok_or_else(|| {
                     reject(
-                        "internalError",
+                        ErrorCode::InternalError,
                         "Failed to create CFDictionary for update query",
                     )
                 })?;
@@ -470,7 +538,7 @@ impl<R: Runtime> Biometry<R> {
                 )
                 .ok_or_else(|| {
                     reject(
-                        "internalError",
+                        ErrorCode::InternalError,
                         "Failed to create CFDictionary for update_dict",
                     )
                 })?;
@@ -482,7 +550,7 @@ impl<R: Runtime> Biometry<R> {
                 Ok(())
             } else {
                 Err(reject(
-                    "keychainError",
+                    ErrorCode::KeychainError,
                     &format!("Error adding item to keychain: {status}"),
                 ))
             }
@@ -490,7 +558,7 @@ impl<R: Runtime> Biometry<R> {
     }
 
     #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
-    pub fn remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
+    pub(crate) fn backend_remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
         unsafe {
             let cf_account: CFRetained<CFString> = CFString::from_str(&options.name);
             let cf_service: CFRetained<CFString> = CFString::from_str(&options.domain);
@@ -524,7 +592,7 @@ impl<R: Runtime> Biometry<R> {
             )
             .ok_or_else(|| {
                 reject(
-                    "internalError",
+                    ErrorCode::InternalError,
                     "Failed to create CFDictionary for delete query",
                 )
             })?;
@@ -535,10 +603,353 @@ impl<R: Runtime> Biometry<R> {
                 Ok(())
             } else {
                 Err(reject(
-                    "keychainError",
+                    ErrorCode::KeychainError,
                     &format!("Error deleting item from keychain: {status}"),
                 ))
             }
         }
     }
+
+    // Renames in place with `SecItemUpdate` on the account / service
+    // attributes. The payload and its access control are untouched, so no
+    // prompt is shown and there is no window where the item exists twice
+    // or not at all.
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub(crate) fn backend_rename_data(
+        &self,
+        _window: WebviewWindow<R>,
+        options: RenameDataOptions,
+    ) -> crate::Result<()> {
+        unsafe {
+            let cf_account: CFRetained<CFString> = CFString::from_str(&options.name);
+            let cf_service: CFRetained<CFString> = CFString::from_str(&options.domain);
+            let cf_new_account: CFRetained<CFString> = CFString::from_str(&options.new_name);
+            let cf_new_service: CFRetained<CFString> = CFString::from_str(&options.new_domain);
+
+            let true_ref = CFBoolean::new(true).as_ref();
+            let q_keys: [&CFType; 4] = [
+                kSecClass.as_ref(),
+                kSecAttrAccount.as_ref(),
+                kSecAttrService.as_ref(),
+                kSecUseDataProtectionKeychain.as_ref(),
+            ];
+            let q_vals: [&CFType; 4] = [
+                kSecClassGenericPassword.as_ref(),
+                cf_account.as_ref(),
+                cf_service.as_ref(),
+                true_ref,
+            ];
+
+            let query = CFDictionary::new(
+                None,
+                q_keys.as_ptr().cast::<*const c_void>().cast_mut(),
+                q_vals.as_ptr().cast::<*const c_void>().cast_mut(),
+                cf_len(q_keys.len())?,
+                std::ptr::addr_of!(kCFCopyStringDictionaryKeyCallBacks),
+                std::ptr::addr_of!(kCFTypeDictionaryValueCallBacks),
+            )
+            .ok_or_else(|| {
+                reject(
+                    ErrorCode::InternalError,
+                    "Failed to create CFDictionary for rename query",
+                )
+            })?;
+
+            let u_keys: [&CFType; 2] = [kSecAttrAccount.as_ref(), kSecAttrService.as_ref()];
+            let u_vals: [&CFType; 2] = [cf_new_account.as_ref(), cf_new_service.as_ref()];
+
+            let update_dict = CFDictionary::new(
+                None,
+                u_keys.as_ptr().cast::<*const c_void>().cast_mut(),
+                u_vals.as_ptr().cast::<*const c_void>().cast_mut(),
+                cf_len(u_keys.len())?,
+                std::ptr::addr_of!(kCFCopyStringDictionaryKeyCallBacks),
+                std::ptr::addr_of!(kCFTypeDictionaryValueCallBacks),
+            )
+            .ok_or_else(|| {
+                reject(
+                    ErrorCode::InternalError,
+                    "Failed to create CFDictionary for rename attributes",
+                )
+            })?;
+
+            let status = SecItemUpdate(&query, &update_dict);
+
+            if status == errSecSuccess {
+                Ok(())
+            } else if status == errSecDuplicateItem {
+                Err(reject(ErrorCode::Conflict, "Target item already exists"))
+            } else if status == errSecItemNotFound {
+                Err(reject(ErrorCode::ItemNotFound, "Item not found"))
+            } else {
+                Err(reject(
+                    ErrorCode::KeychainError,
+                    &format!("Error renaming item in keychain: {status}"),
+                ))
+            }
+        }
+    }
+}
+
+// Keys in the Secure Enclave. It only does P-256, for signing and for ECDH,
+// so Ed25519 keys are sealed in the keychain instead (see `keys`). The
+// private key is generated inside the enclave and never leaves it; each use
+// asks for user presence, like reading an item does.
+impl<R: Runtime> Biometry<R> {
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub(crate) fn create_platform_key(
+        &self,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        name: &str,
+        alg: KeyAlgorithm,
+    ) -> crate::Result<Option<Vec<u8>>> {
+        if alg == KeyAlgorithm::Ed25519 {
+            return Ok(None);
+        }
+        // Replaces a key left behind by a `create_key` whose index update
+        // failed.
+        self.delete_platform_key(domain, name)?;
+        unsafe {
+            let access = SecAccessControl::with_flags(
+                None,
+                kSecAttrAccessibleWhenUnlockedThisDeviceOnly,
+                SecAccessControlCreateFlags::PrivateKeyUsage
+                    | SecAccessControlCreateFlags::UserPresence,
+                std::ptr::null_mut(),
+            )
+            .ok_or_else(|| {
+                reject(
+                    ErrorCode::InternalError,
+                    "Failed to create SecAccessControl",
+                )
+            })?;
+            let true_ref = CFBoolean::new(true).as_ref();
+            let tag = key_tag(domain, name);
+            let private_attrs = cf_dict(
+                &[
+                    kSecAttrIsPermanent.as_ref(),
+                    kSecAttrApplicationTag.as_ref(),
+                    kSecAttrAccessControl.as_ref(),
+                ],
+                &[true_ref, tag.as_ref(), access.as_ref()],
+            );
+            let bits = CFNumber::new_i32(256);
+            let params = cf_dict(
+                &[
+                    kSecAttrKeyType.as_ref(),
+                    kSecAttrKeySizeInBits.as_ref(),
+                    kSecAttrTokenID.as_ref(),
+                    kSecPrivateKeyAttrs.as_ref(),
+                    kSecUseDataProtectionKeychain.as_ref(),
+                ],
+                &[
+                    kSecAttrKeyTypeECSECPrimeRandom.as_ref(),
+                    bits.as_ref(),
+                    kSecAttrTokenIDSecureEnclave.as_ref(),
+                    private_attrs.as_ref(),
+                    true_ref,
+                ],
+            );
+
+            let mut error: *mut CFError = std::ptr::null_mut();
+            let Some(key) = SecKey::new_random_key(params.as_opaque(), &mut error) else {
+                // Macs without a Secure Enclave end up here.
+                let e = cf_error(error, "Secure Enclave key", ErrorCode::KeychainError);
+                log::warn!("sealing the key instead of using the Secure Enclave: {e}");
+                return Ok(None);
+            };
+            let public_key = key.public_key().ok_or_else(|| {
+                reject(
+                    ErrorCode::KeychainError,
+                    "Secure Enclave key has no public key",
+                )
+            })?;
+            let mut error: *mut CFError = std::ptr::null_mut();
+            let point = public_key
+                .external_representation(&mut error)
+                .ok_or_else(|| cf_error(error, "export public key", ErrorCode::KeychainError))?;
+            Ok(Some(point.to_vec()))
+        }
+    }
+
+    /// Signs `payload` with SHA-256 and returns the X9.62 DER signature.
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub(crate) fn platform_sign(
+        &self,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        name: &str,
+        payload: &[u8],
+        reason: String,
+        cancel_title: Option<String>,
+    ) -> crate::Result<Vec<u8>> {
+        unsafe {
+            let key = copy_private_key(domain, name, &reason, cancel_title.as_deref())?;
+            let mut error: *mut CFError = std::ptr::null_mut();
+            key.signature(
+                kSecKeyAlgorithmECDSASignatureMessageX962SHA256,
+                &CFData::from_bytes(payload),
+                &mut error,
+            )
+            .map(|signature| signature.to_vec())
+            .ok_or_else(|| cf_error(error, "sign", ErrorCode::AuthenticationFailed))
+        }
+    }
+
+    /// ECDH with the uncompressed SEC1 point `peer`. Returns the shared
+    /// x-coordinate.
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub(crate) fn platform_key_agreement(
+        &self,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        name: &str,
+        peer: &[u8],
+        reason: String,
+        cancel_title: Option<String>,
+    ) -> crate::Result<SecretBytes> {
+        unsafe {
+            let bits = CFNumber::new_i32(256);
+            let attrs = cf_dict(
+                &[
+                    kSecAttrKeyType.as_ref(),
+                    kSecAttrKeyClass.as_ref(),
+                    kSecAttrKeySizeInBits.as_ref(),
+                ],
+                &[
+                    kSecAttrKeyTypeECSECPrimeRandom.as_ref(),
+                    kSecAttrKeyClassPublic.as_ref(),
+                    bits.as_ref(),
+                ],
+            );
+            let mut error: *mut CFError = std::ptr::null_mut();
+            let peer = SecKey::with_data(&CFData::from_bytes(peer), attrs.as_opaque(), &mut error)
+                .ok_or_else(|| cf_error(error, "ephemeral key", ErrorCode::InvalidInput))?;
+
+            let key = copy_private_key(domain, name, &reason, cancel_title.as_deref())?;
+            let mut error: *mut CFError = std::ptr::null_mut();
+            key.key_exchange_result(
+                kSecKeyAlgorithmECDHKeyExchangeStandard,
+                &peer,
+                cf_dict(&[], &[]).as_opaque(),
+                &mut error,
+            )
+            .map(|shared| SecretBytes::new(shared.to_vec()))
+            .ok_or_else(|| cf_error(error, "key agreement", ErrorCode::AuthenticationFailed))
+        }
+    }
+
+    /// Deletes the key. Succeeds if it doesn't exist.
+    #[allow(clippy::unused_self)]
+    pub(crate) fn delete_platform_key(&self, domain: &str, name: &str) -> crate::Result<()> {
+        unsafe {
+            let tag = key_tag(domain, name);
+            let query = cf_dict(
+                &[
+                    kSecClass.as_ref(),
+                    kSecAttrApplicationTag.as_ref(),
+                    kSecUseDataProtectionKeychain.as_ref(),
+                ],
+                &[
+                    kSecClassKey.as_ref(),
+                    tag.as_ref(),
+                    CFBoolean::new(true).as_ref(),
+                ],
+            );
+            let status = SecItemDelete(query.as_opaque());
+            if status == errSecSuccess || status == errSecItemNotFound {
+                Ok(())
+            } else {
+                Err(reject(
+                    ErrorCode::KeychainError,
+                    &format!("Error deleting key from keychain: {status}"),
+                ))
+            }
+        }
+    }
+}
+
+// Enclave keys are found by application tag, scoped like item names are by
+// `(service, account)`.
+fn key_tag(domain: &str, name: &str) -> CFRetained<CFData> {
+    CFData::from_bytes(
+        format!("{}{}:{domain}:{name}", keys::RESERVED_PREFIX, domain.len()).as_bytes(),
+    )
+}
+
+fn cf_dict(keys: &[&CFType], values: &[&CFType]) -> CFRetained<CFDictionary<CFType, CFType>> {
+    CFDictionary::from_slices(keys, values)
+}
+
+// Looks up the private half of an enclave key. The LAContext carries the
+// prompt text to the signing or key agreement that uses the returned key.
+unsafe fn copy_private_key(
+    domain: &str,
+    name: &str,
+    reason: &str,
+    cancel_title: Option<&str>,
+) -> crate::Result<CFRetained<SecKey>> {
+    let auth_ctx = LAContext::new();
+    auth_ctx.setLocalizedReason(&objc2_foundation::NSString::from_str(reason));
+    if let Some(title) = cancel_title {
+        auth_ctx.setLocalizedCancelTitle(Some(&objc2_foundation::NSString::from_str(title)));
+    }
+    let auth_ctx_cf: &CFType = &*std::ptr::addr_of!(*auth_ctx).cast::<CFType>();
+
+    let true_ref = CFBoolean::new(true).as_ref();
+    let tag = key_tag(domain, name);
+    let query = cf_dict(
+        &[
+            kSecClass.as_ref(),
+            kSecAttrApplicationTag.as_ref(),
+            kSecAttrKeyClass.as_ref(),
+            kSecReturnRef.as_ref(),
+            kSecUseAuthenticationContext.as_ref(),
+            kSecUseDataProtectionKeychain.as_ref(),
+        ],
+        &[
+            kSecClassKey.as_ref(),
+            tag.as_ref(),
+            kSecAttrKeyClassPrivate.as_ref(),
+            true_ref,
+            auth_ctx_cf,
+            true_ref,
+        ],
+    );
+
+    let mut out: *const CFType = std::ptr::null();
+    let status = SecItemCopyMatching(query.as_opaque(), &mut out);
+    if status == errSecItemNotFound {
+        return Err(reject(ErrorCode::ItemNotFound, "Key not found"));
+    }
+    if status != errSecSuccess {
+        return Err(reject(
+            ErrorCode::KeychainError,
+            &format!("Error retrieving key from keychain: {status}"),
+        ));
+    }
+    std::ptr::NonNull::new(out.cast_mut().cast::<SecKey>())
+        .map(|key| CFRetained::from_raw(key))
+        .ok_or_else(|| {
+            reject(
+                ErrorCode::InternalError,
+                "SecItemCopyMatching returned null",
+            )
+        })
+}
+
+// Takes ownership of a CFError out-parameter. A dismissed prompt becomes
+// `userCancel`; anything else gets `code`.
+unsafe fn cf_error(error: *mut CFError, what: &str, code: ErrorCode) -> crate::Error {
+    let Some(error) = std::ptr::NonNull::new(error).map(|e| CFRetained::from_raw(e)) else {
+        return reject(code, &format!("{what} failed"));
+    };
+    if CFIndex::try_from(errSecUserCanceled).is_ok_and(|c| c == error.code()) {
+        return reject(ErrorCode::UserCancel, "User canceled");
+    }
+    let description = error
+        .description()
+        .map_or_else(|| error.code().to_string(), |d| d.to_string());
+    reject(code, &format!("{what} failed: {description}"))
 }
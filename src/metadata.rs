@@ -0,0 +1,557 @@
+//! Non-secret bookkeeping for stored items: creation / update timestamps,
+//! an optional label, a small map of custom string attributes, the optional
+//! expiry used for TTL items and the revision counter behind conditional
+//! writes.
+//!
+//! The platform stores (Keychain, `PasswordVault`, Android `DataStore`) only
+//! hold the protected payload, and most of them can't be read back without
+//! prompting. This index lives in the app's local data directory instead,
+//! so `get_metadata` can answer without touching the secret. Nothing
+//! written here is secret — never put the payload in a label or attribute.
+//!
+//! The index is maintained by the storage methods on `Biometry` (see
+//! [`crate::storage`]), which the commands go through as well, so items
+//! written from Rust are indexed too. Items written before it existed have
+//! no metadata until their next `set_data`.
+
+use std::collections::BTreeMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::ErrorCode;
+use crate::locks::{ItemGuard, ItemLocks};
+use crate::models::{ItemMetadata, SetDataOptions};
+
+const FILE_NAME: &str = "biometry-metadata.json";
+const FILE_VERSION: u8 = 1;
+const MAX_LABEL_LEN: usize = 256;
+const MAX_ATTRIBUTES: usize = 32;
+const MAX_ATTRIBUTE_KEY_LEN: usize = 64;
+const MAX_ATTRIBUTE_VALUE_LEN: usize = 1024;
+
+type Items = BTreeMap<(String, String), ItemMetadata>;
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    v: u8,
+    items: Vec<ItemMetadata>,
+}
+
+/// Persistent metadata index, managed as plugin state.
+pub struct Store {
+    path: Option<PathBuf>,
+    items: Mutex<Items>,
+    // Serializes check-then-write sequences (conditional `set_data`, TTL
+    // purges) on the same item. Separate from `items` because it is held
+    // across backend calls that may sit on a biometric prompt.
+    writers: ItemLocks,
+}
+
+impl Store {
+    /// Loads the index from the app's local data directory. A missing or
+    /// unreadable file starts an empty index rather than failing plugin
+    /// setup — metadata is advisory, the secrets themselves are untouched.
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        Self::open(
+            app.path()
+                .app_local_data_dir()
+                .ok()
+                .map(|dir| dir.join(FILE_NAME)),
+        )
+    }
+
+    // `None` keeps the index in memory only.
+    fn open(path: Option<PathBuf>) -> Self {
+        let items = path
+            .as_ref()
+            .and_then(|p| std::fs::read(p).ok())
+            .and_then(|bytes| match serde_json::from_slice::<IndexFile>(&bytes) {
+                Ok(file) if file.v == FILE_VERSION => Some(file.items),
+                Ok(file) => {
+                    log::warn!("ignoring biometry metadata index version {}", file.v);
+                    None
+                }
+                Err(e) => {
+                    log::warn!("ignoring unreadable biometry metadata index: {e}");
+                    None
+                }
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| ((m.domain.clone(), m.name.clone()), m))
+            .collect();
+
+        Self {
+            path,
+            items: Mutex::new(items),
+            writers: ItemLocks::default(),
+        }
+    }
+
+    /// Takes the write lock for one item. Hold it from the precondition
+    /// check until the matching `record_write` / `remove` so no other write
+    /// to the item can land in between.
+    pub fn writer(&self, domain: &str, name: &str) -> ItemGuard<'_> {
+        self.writers.lock(domain, name)
+    }
+
+    /// Takes the write locks for several items at once.
+    pub fn writers(&self, items: &[(&str, &str)]) -> ItemGuard<'_> {
+        self.writers.lock_all(items)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Items> {
+        self.items.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub fn get(&self, domain: &str, name: &str) -> Option<ItemMetadata> {
+        self.lock()
+            .get(&(domain.to_string(), name.to_string()))
+            .cloned()
+    }
+
+    /// Every entry in `domain`, ordered by name.
+    pub fn in_domain(&self, domain: &str) -> Vec<ItemMetadata> {
+        self.lock()
+            .values()
+            .filter(|m| m.domain == domain)
+            .cloned()
+            .collect()
+    }
+
+    pub fn is_expired(&self, domain: &str, name: &str, now: u64) -> bool {
+        self.lock()
+            .get(&(domain.to_string(), name.to_string()))
+            .is_some_and(|m| m.expires_at.is_some_and(|t| t <= now))
+    }
+
+    /// `(domain, name)` of every item whose expiry is at or before `now`.
+    pub fn expired(&self, now: u64) -> Vec<(String, String)> {
+        self.lock()
+            .iter()
+            .filter(|(_, m)| m.expires_at.is_some_and(|t| t <= now))
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// Records a successful `set_data`. Keeps `created_at` of an existing
+    /// entry, bumps its `revision` and replaces everything else.
+    // The guard is deliberately held across `persist` (see below).
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn record_write(&self, options: &SetDataOptions) -> crate::Result<()> {
+        let now = now_ms();
+        let mut items = self.lock();
+        let key = (options.domain.clone(), options.name.clone());
+        let (created_at, revision) = items
+            .get(&key)
+            .map_or((now, 1), |m| (m.created_at, m.revision + 1));
+        items.insert(
+            key,
+            ItemMetadata {
+                domain: options.domain.clone(),
+                name: options.name.clone(),
+                created_at,
+                updated_at: now,
+                revision,
+                label: options.label.clone(),
+                attributes: options.attributes.clone(),
+                expires_at: expiry(options, now),
+                rust_only: options.rust_only == Some(true),
+            },
+        );
+        self.persist(&items)
+    }
+
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn remove(&self, domain: &str, name: &str) -> crate::Result<()> {
+        let mut items = self.lock();
+        if items
+            .remove(&(domain.to_string(), name.to_string()))
+            .is_none()
+        {
+            return Ok(());
+        }
+        self.persist(&items)
+    }
+
+    /// Moves the entry for a renamed item to its new key. The item keeps its
+    /// creation time, label, attributes and expiry; the rename itself counts
+    /// as a write. Items without an entry stay without one.
+    #[allow(clippy::significant_drop_tightening)]
+    pub fn rename(
+        &self,
+        domain: &str,
+        name: &str,
+        new_domain: &str,
+        new_name: &str,
+    ) -> crate::Result<()> {
+        let mut items = self.lock();
+        let Some(mut meta) = items.remove(&(domain.to_string(), name.to_string())) else {
+            return Ok(());
+        };
+        meta.domain = new_domain.to_string();
+        meta.name = new_name.to_string();
+        meta.updated_at = now_ms();
+        meta.revision += 1;
+        items.insert((new_domain.to_string(), new_name.to_string()), meta);
+        self.persist(&items)
+    }
+
+    // Called with the lock still held so concurrent writers hit the disk in
+    // the same order they updated the map.
+    fn persist(&self, items: &Items) -> crate::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = IndexFile {
+            v: FILE_VERSION,
+            items: items.values().cloned().collect(),
+        };
+        let bytes = serde_json::to_vec(&file).map_err(std::io::Error::other)?;
+        write_atomically(path, &bytes)?;
+        Ok(())
+    }
+}
+
+/// Write-then-rename so a crash mid-write leaves the previous file in place
+/// instead of a truncated one. The data goes to a fresh owner-only temp file
+/// (see [`create_temp`]), which is synced before the rename; the directory
+/// is synced after it so the rename itself survives a crash.
+pub fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let (tmp, mut file) = create_temp(path)?;
+    let written = file
+        .write_all(bytes)
+        .and_then(|()| file.sync_all())
+        .and_then(|()| std::fs::rename(&tmp, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    written?;
+    sync_dir(path)
+}
+
+/// How many random names [`create_temp`] tries before giving up.
+const TEMP_ATTEMPTS: usize = 16;
+
+/// Creates an empty temp file next to `path` under a random name. It is
+/// opened with `create_new`, so an existing file or symlink is never
+/// reused, and is owner-only on Unix whatever the umask. The caller owns
+/// the returned path and is the only one that may remove it.
+pub fn create_temp(path: &Path) -> std::io::Result<(PathBuf, std::fs::File)> {
+    let name = path.file_name().unwrap_or_default();
+    let mut last = None;
+    for _ in 0..TEMP_ATTEMPTS {
+        let mut suffix = [0u8; 8];
+        rand::rng().fill(&mut suffix);
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(name);
+        tmp_name.push(format!(".{:016x}.tmp", u64::from_le_bytes(suffix)));
+        let tmp = path.with_file_name(tmp_name);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&tmp) {
+            Ok(file) => {
+                #[cfg(unix)]
+                if let Err(err) =
+                    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))
+                {
+                    let _ = std::fs::remove_file(&tmp);
+                    return Err(err);
+                }
+                return Ok((tmp, file));
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => last = Some(err),
+            Err(err) => return Err(err),
+        }
+    }
+    Err(last.unwrap_or_else(|| std::io::ErrorKind::AlreadyExists.into()))
+}
+
+/// Makes a rename into `path`'s directory durable. Only Unix can open a
+/// directory to sync it.
+pub fn sync_dir(path: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Rejects labels / attributes that don't fit the "small map of strings"
+/// the index is meant for, and contradictory or already-past expiries,
+/// before anything is written.
+pub fn validate(options: &SetDataOptions) -> crate::Result<()> {
+    match (options.expires_at, options.ttl) {
+        (Some(_), Some(_)) => return Err(invalid("expiresAt and ttl are mutually exclusive")),
+        (Some(t), None) if t <= now_ms() => return Err(invalid("expiresAt is in the past")),
+        (None, Some(0)) => return Err(invalid("ttl must be greater than zero")),
+        _ => {}
+    }
+    if options
+        .label
+        .as_ref()
+        .is_some_and(|l| l.len() > MAX_LABEL_LEN)
+    {
+        return Err(invalid("label exceeds maximum length"));
+    }
+    if options.attributes.len() > MAX_ATTRIBUTES {
+        return Err(invalid("too many attributes"));
+    }
+    for (key, value) in &options.attributes {
+        if key.is_empty() || key.len() > MAX_ATTRIBUTE_KEY_LEN {
+            return Err(invalid("attribute key must be 1-64 bytes"));
+        }
+        if value.len() > MAX_ATTRIBUTE_VALUE_LEN {
+            return Err(invalid("attribute value exceeds maximum length"));
+        }
+    }
+    Ok(())
+}
+
+/// The absolute expiry `options` asks for, resolving a TTL against `now`.
+pub fn expiry(options: &SetDataOptions, now: u64) -> Option<u64> {
+    options
+        .expires_at
+        .or_else(|| options.ttl.map(|ttl| now.saturating_add(ttl)))
+}
+
+fn invalid(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InvalidInput, message)
+}
+
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(domain: &str, name: &str) -> SetDataOptions {
+        SetDataOptions {
+            domain: domain.to_string(),
+            name: name.to_string(),
+            ..SetDataOptions::default()
+        }
+    }
+
+    fn snapshot(store: &Store) -> serde_json::Value {
+        serde_json::to_value(store.lock().values().collect::<Vec<_>>()).expect("serialize")
+    }
+
+    #[test]
+    fn index_round_trips_through_the_file() {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-biometry-metadata-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(FILE_NAME);
+
+        let store = Store::open(Some(path.clone()));
+        let token = SetDataOptions {
+            label: Some("API token".to_string()),
+            attributes: BTreeMap::from([("env".to_string(), "prod".to_string())]),
+            ttl: Some(60_000),
+            ..options("com.example.app", "token")
+        };
+        store.record_write(&token).expect("write");
+        let first = store.get("com.example.app", "token").expect("entry");
+        assert_eq!(first.revision, 1);
+        store.record_write(&token).expect("rewrite");
+        let second = store.get("com.example.app", "token").expect("entry");
+        assert_eq!(second.revision, 2);
+        assert_eq!(second.created_at, first.created_at);
+        assert_eq!(second.label.as_deref(), Some("API token"));
+        assert!(second.expires_at.is_some());
+
+        store
+            .record_write(&options("com.example.app", "old"))
+            .expect("write");
+        store
+            .rename("com.example.app", "old", "com.example.other", "new")
+            .expect("rename");
+        assert!(store.get("com.example.app", "old").is_none());
+        assert_eq!(
+            store
+                .get("com.example.other", "new")
+                .expect("moved")
+                .revision,
+            2
+        );
+        store.remove("com.example.app", "missing").expect("remove");
+
+        let reloaded = Store::open(Some(path.clone()));
+        assert_eq!(snapshot(&reloaded), snapshot(&store));
+        assert_eq!(reloaded.in_domain("com.example.app").len(), 1);
+
+        reloaded.remove("com.example.app", "token").expect("remove");
+        assert!(Store::open(Some(path))
+            .get("com.example.app", "token")
+            .is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unreadable_or_newer_indexes_start_empty() {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-biometry-metadata-bad-{}",
+            std::process::id()
+        ));
+        let path = dir.join(FILE_NAME);
+        let newer = format!(
+            r#"{{"v":{},"items":[{{"domain":"d","name":"n","createdAt":1,"updatedAt":1,"label":null}}]}}"#,
+            FILE_VERSION + 1
+        );
+        for contents in [b"not json".as_slice(), newer.as_bytes()] {
+            write_atomically(&path, contents).expect("write");
+            assert!(Store::open(Some(path.clone())).lock().is_empty());
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn expiry_is_tracked_per_item() {
+        let store = Store::open(None);
+        let now = now_ms();
+        store
+            .record_write(&SetDataOptions {
+                expires_at: Some(now - 1),
+                ..options("d", "stale")
+            })
+            .expect("write");
+        store
+            .record_write(&SetDataOptions {
+                ttl: Some(60_000),
+                ..options("d", "fresh")
+            })
+            .expect("write");
+        store.record_write(&options("d", "forever")).expect("write");
+
+        assert_eq!(store.expired(now), [("d".to_string(), "stale".to_string())]);
+        assert!(store.is_expired("d", "stale", now));
+        assert!(!store.is_expired("d", "fresh", now));
+        assert!(store.is_expired("d", "fresh", now + 60_000));
+        assert!(!store.is_expired("d", "forever", u64::MAX));
+        assert!(!store.is_expired("d", "missing", u64::MAX));
+
+        store.remove("d", "stale").expect("remove");
+        assert!(store.expired(now).is_empty());
+    }
+
+    #[test]
+    fn contradictory_or_past_expiries_are_rejected() {
+        let now = now_ms();
+        assert!(validate(&SetDataOptions {
+            ttl: Some(1),
+            ..options("d", "n")
+        })
+        .is_ok());
+        for bad in [
+            SetDataOptions {
+                expires_at: Some(now + 60_000),
+                ttl: Some(60_000),
+                ..options("d", "n")
+            },
+            SetDataOptions {
+                expires_at: Some(now - 1),
+                ..options("d", "n")
+            },
+            SetDataOptions {
+                ttl: Some(0),
+                ..options("d", "n")
+            },
+            SetDataOptions {
+                attributes: BTreeMap::from([(String::new(), "v".to_string())]),
+                ..options("d", "n")
+            },
+        ] {
+            assert_eq!(
+                validate(&bad).expect_err("valid").code(),
+                ErrorCode::InvalidInput
+            );
+        }
+        assert_eq!(
+            expiry(
+                &SetDataOptions {
+                    ttl: Some(u64::MAX),
+                    ..options("d", "n")
+                },
+                now
+            ),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn atomic_writes_replace_the_file_and_leave_no_temp_behind() {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-biometry-metadata-atomic-{}",
+            std::process::id()
+        ));
+        let path = dir.join(FILE_NAME);
+        std::fs::create_dir_all(&dir).expect("dir");
+        std::fs::write(&path, b"old").expect("write");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).expect("chmod");
+        }
+
+        write_atomically(&path, b"new").expect("write");
+
+        assert_eq!(std::fs::read(&path).expect("read"), b"new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path)
+                .expect("metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let names: Vec<_> = std::fs::read_dir(&dir)
+            .expect("read dir")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect();
+        assert_eq!(names, [FILE_NAME]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn temp_files_are_fresh_and_distinct() {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-biometry-metadata-temp-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).expect("dir");
+        let path = dir.join(FILE_NAME);
+
+        let (first, _) = create_temp(&path).expect("temp");
+        let (second, _) = create_temp(&path).expect("temp");
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(dir.as_path()));
+        assert_eq!(std::fs::read(&first).expect("read"), b"");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,13 +1,17 @@
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tauri::{
     plugin::{PluginApi, PluginHandle},
     AppHandle, Runtime, WebviewWindow,
 };
 
+use crate::b64_field;
+use crate::error::ErrorCode;
 use crate::models::{
     AuthOptions, AuthenticatePayload, DataOptions, DataResponse, GetDataOptions, HasDataResponse,
-    RemoveDataOptions, SetDataOptions, Status,
+    KeyAlgorithm, RemoveDataOptions, RenameDataOptions, SetDataOptions, Status,
 };
+use crate::payload;
+use crate::sensitive::SecretBytes;
 
 #[cfg(target_os = "android")]
 const PLUGIN_IDENTIFIER: &str = "app.tauri.biometry";
@@ -33,8 +37,16 @@ pub fn init<R: Runtime, C: DeserializeOwned>(
 pub struct Biometry<R: Runtime>(PluginHandle<R>);
 
 impl<R: Runtime> Biometry<R> {
+    pub(crate) fn app(&self) -> &AppHandle<R> {
+        self.0.app()
+    }
+
     pub fn status(&self) -> crate::Result<Status> {
-        self.0.run_mobile_plugin("status", ()).map_err(Into::into)
+        let mut status: Status = self.0.run_mobile_plugin("status", ())?;
+        // Keys are sealed into ordinary items, so they work wherever
+        // storage does.
+        status.capabilities.keys = status.capabilities.storage;
+        Ok(status)
     }
 
     pub fn authenticate(
@@ -48,36 +60,184 @@ impl<R: Runtime> Biometry<R> {
             .map_err(Into::into)
     }
 
-    pub fn has_data(&self, options: DataOptions) -> crate::Result<bool> {
+    pub(crate) fn backend_has_data(&self, options: DataOptions) -> crate::Result<bool> {
         self.0
             .run_mobile_plugin("hasData", options)
             .map(|result: HasDataResponse| result.has_data)
             .map_err(Into::into)
     }
 
-    pub fn get_data(
+    pub(crate) fn backend_get_data(
         &self,
         _window: WebviewWindow<R>,
         options: GetDataOptions,
     ) -> crate::Result<DataResponse> {
-        self.0
-            .run_mobile_plugin("getData", options)
-            .map_err(Into::into)
+        let response: DataResponse = self.0.run_mobile_plugin("getData", options)?;
+        Ok(payload::response(
+            response.domain,
+            response.name,
+            response.data,
+        ))
     }
 
-    pub fn set_data(
+    pub(crate) fn backend_set_data(
         &self,
         _window: WebviewWindow<R>,
         options: SetDataOptions,
     ) -> crate::Result<()> {
+        let options = SetDataOptions {
+            data: payload::stored(&options),
+            ..options
+        };
         self.0
             .run_mobile_plugin("setData", options)
             .map_err(Into::into)
     }
 
-    pub fn remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
+    pub(crate) fn backend_remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
         self.0
             .run_mobile_plugin("removeData", options)
             .map_err(Into::into)
     }
+
+    pub(crate) fn backend_rename_data(
+        &self,
+        _window: WebviewWindow<R>,
+        options: RenameDataOptions,
+    ) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("renameData", options)
+            .map_err(Into::into)
+    }
+
+    // The Secure Enclave signs and does ECDH on P-256. On Android only
+    // signing keys go to the Keystore: with androidx.biometric 1.1 a prompt
+    // can unlock a `Signature` but not a `KeyAgreement`. Everything else,
+    // and devices without the hardware, fall back to a sealed key.
+    pub(crate) fn create_platform_key(
+        &self,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        name: &str,
+        alg: KeyAlgorithm,
+    ) -> crate::Result<Option<Vec<u8>>> {
+        let supported = match alg {
+            KeyAlgorithm::P256 => true,
+            KeyAlgorithm::EcdhP256 => cfg!(target_os = "ios"),
+            KeyAlgorithm::Ed25519 => false,
+        };
+        if !supported {
+            return Ok(None);
+        }
+        match self
+            .0
+            .run_mobile_plugin::<NativePublicKey>("createKey", NativeKey { domain, name })
+        {
+            Ok(key) => Ok(Some(key.public_key)),
+            Err(e) => {
+                let e = crate::Error::from(e);
+                if e.code() == ErrorCode::NotSupported {
+                    Ok(None)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
+    /// Signs `payload` with SHA-256 and returns the X9.62 DER signature.
+    pub(crate) fn platform_sign(
+        &self,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        name: &str,
+        payload: &[u8],
+        reason: String,
+        cancel_title: Option<String>,
+    ) -> crate::Result<Vec<u8>> {
+        self.0
+            .run_mobile_plugin(
+                "signWithKey",
+                NativeKeyUse {
+                    domain,
+                    name,
+                    data: payload,
+                    reason,
+                    cancel_title,
+                },
+            )
+            .map(|result: NativeSignature| result.signature)
+            .map_err(Into::into)
+    }
+
+    /// ECDH with the uncompressed SEC1 point `peer`. Returns the shared
+    /// x-coordinate.
+    pub(crate) fn platform_key_agreement(
+        &self,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        name: &str,
+        peer: &[u8],
+        reason: String,
+        cancel_title: Option<String>,
+    ) -> crate::Result<SecretBytes> {
+        self.0
+            .run_mobile_plugin(
+                "keyAgreement",
+                NativeKeyUse {
+                    domain,
+                    name,
+                    data: peer,
+                    reason,
+                    cancel_title,
+                },
+            )
+            .map(|result: NativeSharedSecret| result.shared_secret)
+            .map_err(Into::into)
+    }
+
+    /// Deletes the key. Succeeds if it doesn't exist.
+    pub(crate) fn delete_platform_key(&self, domain: &str, name: &str) -> crate::Result<()> {
+        self.0
+            .run_mobile_plugin("deleteKey", NativeKey { domain, name })
+            .map_err(Into::into)
+    }
+}
+
+// Arguments and results of the native key commands. Bytes travel as base64.
+#[derive(Serialize)]
+struct NativeKey<'a> {
+    domain: &'a str,
+    name: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeKeyUse<'a> {
+    domain: &'a str,
+    name: &'a str,
+    #[serde(with = "b64_field")]
+    data: &'a [u8],
+    reason: String,
+    cancel_title: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NativePublicKey {
+    #[serde(with = "b64_field")]
+    public_key: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+struct NativeSignature {
+    #[serde(with = "b64_field")]
+    signature: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeSharedSecret {
+    #[serde(with = "b64_field")]
+    shared_secret: SecretBytes,
 }
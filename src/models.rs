@@ -1,5 +1,11 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
+use crate::b64_field;
+use crate::sensitive::{SecretBytes, SecretString};
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthOptions {
@@ -24,13 +30,64 @@ pub struct AuthenticatePayload {
     pub options: AuthOptions,
 }
 
-#[derive(Debug, Clone, serde_repr::Deserialize_repr, serde_repr::Serialize_repr)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde_repr::Deserialize_repr, serde_repr::Serialize_repr,
+)]
 #[repr(u8)]
 pub enum BiometryType {
     None = 0,
+    /// Windows Hello is available but no biometric sensor was found, e.g.
+    /// PIN only, or the sensors couldn't be enumerated.
     Auto = 1,
     TouchID = 2,
     FaceID = 3,
+    Iris = 4,
+    OpticID = 5,
+    /// A fingerprint reader (Android, Windows).
+    Fingerprint = 6,
+    /// Face recognition (Android, Windows).
+    Face = 7,
+    /// More than one modality; see `Status::modalities`.
+    Multiple = 8,
+}
+
+/// How strongly the available biometry identifies the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BiometryStrength {
+    /// Strong enough to unlock hardware-backed keys (Android Class 3,
+    /// Touch ID, Face ID).
+    Strong,
+    /// Android Class 2: fine for `authenticate`, but not for storage.
+    Weak,
+    /// Biometry is available but the platform doesn't report its class
+    /// (Windows Hello).
+    Unknown,
+    /// No biometry is available.
+    None,
+}
+
+/// What the current machine can do, so a UI can hide features that would
+/// only fail.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    /// `set_data` / `get_data` and the other storage commands work.
+    pub storage: bool,
+    /// A device passcode, PIN or password can stand in for biometry
+    /// (`allow_device_credential`).
+    pub device_credential: bool,
+    /// The key, `derive_key` and OTP commands work. Filled in on the Rust
+    /// side, since those secrets are sealed through the storage backend.
+    #[serde(default)]
+    pub keys: bool,
+    pub strength: BiometryStrength,
+    /// The platform backend: `keychain` (macOS, iOS), `windowsHello`,
+    /// `androidKeystore` or `none` (Linux).
+    pub backend: String,
+    /// The OS version on macOS and iOS, the API level on Android and the
+    /// `webauthn.dll` API version, e.g. `8`, on Windows.
+    pub backend_version: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -40,6 +97,19 @@ pub struct Status {
     pub biometry_type: BiometryType,
     pub error: Option<String>,
     pub error_code: Option<String>,
+    /// Every biometric modality the device offers, empty when biometry is
+    /// unavailable. `biometry_type` is `Multiple` when there is more than
+    /// one.
+    pub modalities: Vec<BiometryType>,
+    pub capabilities: Capabilities,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusOptions {
+    /// Probe the platform even if a cached status is available.
+    #[serde(default)]
+    pub fresh: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -60,7 +130,29 @@ pub struct DataOptions {
 pub struct DataResponse {
     pub domain: String,
     pub name: String,
-    pub data: String,
+    pub data: SecretString,
+    /// Whether the item was written with `SetDataOptions::rust_only`. Only
+    /// ever `true` on the Rust side: the `get_data` command refuses such
+    /// items.
+    #[serde(skip)]
+    pub rust_only: bool,
+    /// Expiry carried in the protected payload, in milliseconds since the
+    /// Unix epoch. `get_data` never returns an item past it.
+    #[serde(skip)]
+    pub expires_at: Option<u64>,
+}
+
+/// What the `get_data` command returns: `data`, or `handle` in its place
+/// when the request asked for one (see `GetDataOptions::handle`).
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDataResponse {
+    pub domain: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<SecretString>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -70,14 +162,407 @@ pub struct GetDataOptions {
     pub name: String,
     pub reason: String,
     pub cancel_title: Option<String>,
+    /// Return an opaque handle in place of the data, for Rust code to
+    /// resolve with `app.biometry().resolve_handle(..)`. Only honored by the
+    /// `get_data` command.
+    #[serde(default)]
+    pub handle: Option<HandleOptions>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandleOptions {
+    /// Lifetime in milliseconds. Defaults to one minute, at most five.
+    #[serde(default)]
+    pub ttl: Option<u64>,
+    /// Whether the first successful `resolve_handle` consumes the handle. Defaults to `true`.
+    #[serde(default)]
+    pub single_use: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SetDataOptions {
     pub domain: String,
     pub name: String,
-    pub data: String,
+    pub data: SecretString,
+    /// Optional human-readable label, returned by `get_metadata`. Replaces the previous label on every write.
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Custom string attributes, returned by `get_metadata`. Replaces the previous attributes on every write.
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+    /// Absolute expiry, in milliseconds since the Unix epoch. Mutually exclusive with `ttl`.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// Lifetime in milliseconds, counted from this write. Mutually exclusive with `expires_at`.
+    #[serde(default)]
+    pub ttl: Option<u64>,
+    /// Only write if no item exists yet; fails with `conflict` otherwise.
+    #[serde(default)]
+    pub if_absent: Option<bool>,
+    /// Only write if the item's current `revision` equals this value; fails with `conflict` otherwise.
+    #[serde(default)]
+    pub if_version: Option<u64>,
+    /// Make the item readable only through `app.biometry().get_data(..)` in Rust. The `get_data` command fails with `rustOnly`.
+    #[serde(default)]
+    pub rust_only: Option<bool>,
+}
+
+/// Non-secret bookkeeping kept for every item written through the storage commands.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemMetadata {
+    pub domain: String,
+    pub name: String,
+    /// Time of the first write, in milliseconds since the Unix epoch.
+    pub created_at: u64,
+    /// Time of the latest write, in milliseconds since the Unix epoch.
+    pub updated_at: u64,
+    /// Incremented on every write, starting at 1. Use with `SetDataOptions::if_version`.
+    #[serde(default)]
+    pub revision: u64,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub attributes: BTreeMap<String, String>,
+    /// Expiry time, in milliseconds since the Unix epoch. Expired items are treated as absent and deleted on next access.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+    /// Written with `SetDataOptions::rust_only`.
+    #[serde(default)]
+    pub rust_only: bool,
 }
 
 pub type RemoveDataOptions = DataOptions;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenameDataOptions {
+    pub domain: String,
+    pub name: String,
+    pub new_domain: String,
+    pub new_name: String,
+    /// Shown in the authentication prompt on Android, where the item has to
+    /// be decrypted to move it.
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub cancel_title: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeExpiredOptions {
+    /// Restricts the purge to one domain. Omit to purge every domain the scope allows.
+    pub domain: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDomainOptions {
+    pub domain: String,
+    /// Passphrase the archive is encrypted under. Needed again to import it.
+    pub passphrase: SecretString,
+    /// Shown in the authentication prompt for each item read.
+    pub reason: String,
+    pub cancel_title: Option<String>,
+    /// Items to export. Needed for items the metadata index doesn't know
+    /// about, such as ones written before it existed. Omit to export every
+    /// indexed item in the domain.
+    #[serde(default)]
+    pub names: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDomainResponse {
+    /// Archive text for `import_domain`.
+    pub archive: String,
+    /// Items left out because they are Rust-only or no longer exist.
+    pub skipped: Vec<DataOptions>,
+    /// Exported items that had no metadata index entry. They are only found
+    /// when listed in `names`.
+    pub unindexed: Vec<DataOptions>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportDomainOptions {
+    /// Archive text returned by `export_domain`.
+    pub archive: String,
+    pub passphrase: SecretString,
+    /// Replace items that already exist locally. By default they are skipped.
+    #[serde(default)]
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportDomainResponse {
+    pub imported: Vec<DataOptions>,
+    /// Items left alone because they already exist locally or have expired.
+    pub skipped: Vec<DataOptions>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchWithSecretOptions {
+    pub domain: String,
+    pub name: String,
+    pub reason: String,
+    pub cancel_title: Option<String>,
+    /// Absolute `http` or `https` URL. Must be listed in the matching scope entry's `urls`.
+    pub url: String,
+    /// Request method. Defaults to `GET`.
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Additional request headers, sent as-is.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Header that carries the secret, e.g. `Authorization`.
+    pub secret_header: String,
+    /// Value of `secret_header`, with `{secret}` replaced by the item's data, e.g. `Bearer {secret}`.
+    pub template: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Timeout in milliseconds, covering the whole request. Defaults to 30 seconds.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FetchResponse {
+    pub status: u16,
+    /// Response headers by lowercase name. Repeated headers are joined with `, `.
+    pub headers: BTreeMap<String, String>,
+    /// Response body, decoded as UTF-8 text. At most 8 MiB.
+    pub body: String,
+}
+
+/// Algorithm of a key created with `create_key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum KeyAlgorithm {
+    /// ECDSA over NIST P-256 with SHA-256.
+    #[serde(rename = "P-256")]
+    P256,
+    Ed25519,
+    /// P-256 key for `encrypt` / `decrypt` (ECDH-ES, HKDF-SHA-256, AES-256-GCM). Cannot sign.
+    #[serde(rename = "ECDH-P-256")]
+    EcdhP256,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateKeyOptions {
+    pub domain: String,
+    pub name: String,
+    pub alg: KeyAlgorithm,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignOptions {
+    pub domain: String,
+    pub name: String,
+    /// Bytes to sign. Hashed with SHA-256 for P-256; signed as-is for Ed25519.
+    /// Base64 over IPC, like every byte field.
+    #[serde(with = "b64_field")]
+    pub payload: Vec<u8>,
+    pub reason: String,
+    pub cancel_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignResponse {
+    /// Raw `r || s` for P-256, 64 bytes for Ed25519. Base64 over IPC.
+    #[serde(with = "b64_field")]
+    pub signature: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptOptions {
+    pub domain: String,
+    pub name: String,
+    /// Base64 over IPC, so a large payload isn't sent as an array of numbers.
+    #[serde(with = "b64_field")]
+    pub plaintext: SecretBytes,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecryptResponse {
+    /// Base64 over IPC, like `EncryptOptions::plaintext`.
+    #[serde(with = "b64_field")]
+    pub plaintext: SecretBytes,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecryptOptions {
+    pub domain: String,
+    pub name: String,
+    /// Envelope returned by `encrypt`.
+    pub ciphertext: String,
+    pub reason: String,
+    pub cancel_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptFileOptions {
+    pub domain: String,
+    /// Name of an `ECDH-P-256` key.
+    pub name: String,
+    /// File to encrypt. Must be allowed by the fs plugin scope.
+    pub input: PathBuf,
+    /// Where to write the encrypted file. Replaced if it exists.
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecryptFileOptions {
+    pub domain: String,
+    pub name: String,
+    /// File written by `encrypt_file`.
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub reason: String,
+    pub cancel_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeriveKeyOptions {
+    pub domain: String,
+    /// Distinguishes keys derived in the same domain, e.g. `"database"`.
+    pub context: String,
+    /// Output length in bytes.
+    pub length: usize,
+    pub reason: String,
+    pub cancel_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeriveKeyResponse {
+    /// Base64 over IPC, like `DecryptResponse::plaintext`.
+    #[serde(with = "b64_field")]
+    pub key: SecretBytes,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OtpKind {
+    /// RFC 6238 time-based codes.
+    Totp,
+    /// RFC 4226 counter-based codes.
+    Hotp,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum OtpAlgorithm {
+    #[serde(rename = "SHA1")]
+    Sha1,
+    #[serde(rename = "SHA256")]
+    Sha256,
+    #[serde(rename = "SHA512")]
+    Sha512,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOtpOptions {
+    pub domain: String,
+    pub name: String,
+    /// An `otpauth://totp/...` or `otpauth://hotp/...` URI.
+    pub uri: SecretString,
+    /// Replace an existing OTP with the same `(domain, name)`. Defaults to
+    /// `false`, which fails with `conflict`.
+    #[serde(default)]
+    pub overwrite: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateOtpOptions {
+    pub domain: String,
+    pub name: String,
+    pub reason: String,
+    pub cancel_title: Option<String>,
+}
+
+pub type OtpOptions = DataOptions;
+
+/// The non-secret parameters of an imported OTP.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtpInfo {
+    pub domain: String,
+    pub name: String,
+    pub kind: OtpKind,
+    pub algorithm: OtpAlgorithm,
+    pub digits: u32,
+    /// TOTP time step in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<u64>,
+    /// HOTP counter the next code will use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Import time, in milliseconds since the Unix epoch.
+    pub created_at: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtpCode {
+    pub code: String,
+    /// TOTP: when the code stops being current, in milliseconds since the
+    /// Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    /// HOTP: the counter value the code was generated for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counter: Option<u64>,
+}
+
+pub type KeyOptions = DataOptions;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialKeyOptions {
+    pub domain: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicKeyResponse {
+    pub domain: String,
+    pub name: String,
+    pub alg: KeyAlgorithm,
+    /// Uncompressed SEC1 point (65 bytes) for P-256 and ECDH-P-256, raw 32-byte key for Ed25519.
+    /// Base64 over IPC, as are `spki` and `cose_key`.
+    #[serde(with = "b64_field")]
+    pub public_key: Vec<u8>,
+    /// DER-encoded `SubjectPublicKeyInfo`.
+    #[serde(with = "b64_field")]
+    pub spki: Vec<u8>,
+    /// PEM (`PUBLIC KEY`) of `spki`.
+    pub pem: String,
+    pub jwk: crate::encoding::Jwk,
+    /// CBOR-encoded `COSE_Key`.
+    #[serde(with = "b64_field")]
+    pub cose_key: Vec<u8>,
+    /// RFC 7638 JWK thumbprint (base64url SHA-256). Stable for the life of the key.
+    pub thumbprint: String,
+    /// Whether the private key is held non-exportably by the platform
+    /// (Secure Enclave, Android Keystore) rather than sealed as a stored item.
+    pub platform: bool,
+}
@@ -0,0 +1,615 @@
+//! One-time passwords for `import_otp` / `generate_otp` / `delete_otp`:
+//! HOTP (RFC 4226) and TOTP (RFC 6238) codes computed in Rust from a seed
+//! the webview never sees.
+//!
+//! The seed is sealed as an ordinary item — same `domain`, name prefixed
+//! with [`RESERVED_PREFIX`] — so every `generate_otp` goes through the same
+//! user verification as `get_data`. The parameters, including the HOTP
+//! counter (which is not secret), live in a sidecar index next to the
+//! metadata index, so the counter can move without re-sealing the seed. The
+//! counter is advanced and written to disk before a code is returned: a
+//! crash can skip a value, which servers tolerate, but never reuse one.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use hmac::{digest::KeyInit, Hmac, Mac};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, Runtime, Url, WebviewWindow};
+use zeroize::Zeroizing;
+
+use crate::b64_field;
+use crate::error::ErrorCode;
+use crate::locks::{ItemGuard, ItemLocks};
+use crate::models::{
+    GenerateOtpOptions, ImportOtpOptions, OtpAlgorithm, OtpCode, OtpInfo, OtpKind,
+};
+use crate::sensitive::{SecretBytes, SecretString};
+use crate::{metadata, secret};
+
+/// Item names starting with this are reserved for sealed OTP seeds and
+/// rejected by the data commands (see [`secret::check_not_reserved`]).
+pub const RESERVED_PREFIX: &str = "biometry.otp:";
+
+const FILE_NAME: &str = "biometry-otp.json";
+const FILE_VERSION: u8 = 1;
+const SEED_VERSION: u8 = 1;
+
+const DEFAULT_DIGITS: u32 = 6;
+const DEFAULT_PERIOD: u64 = 30;
+const MIN_DIGITS: u32 = 6;
+const MAX_DIGITS: u32 = 8;
+const MAX_PERIOD: u64 = 24 * 60 * 60;
+const MAX_SEED_LEN: usize = 128;
+
+type Records = BTreeMap<(String, String), OtpInfo>;
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    v: u8,
+    otps: Vec<OtpInfo>,
+}
+
+// What gets sealed in the platform store.
+#[derive(Serialize, Deserialize)]
+struct SealedSeed {
+    v: u8,
+    #[serde(with = "b64_field")]
+    seed: SecretBytes,
+}
+
+/// OTP parameter index, managed as plugin state.
+pub struct Store {
+    path: Option<PathBuf>,
+    otps: Mutex<Records>,
+    // Held per OTP across import / delete, which span a backend call and an
+    // index update, and across each HOTP counter bump.
+    writers: ItemLocks,
+}
+
+impl Store {
+    /// Loads the index from the app's local data directory, starting empty
+    /// if it is missing or unreadable.
+    pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+        Self::open(
+            app.path()
+                .app_local_data_dir()
+                .ok()
+                .map(|dir| dir.join(FILE_NAME)),
+        )
+    }
+
+    // `None` keeps the index in memory only.
+    fn open(path: Option<PathBuf>) -> Self {
+        let otps = path
+            .as_ref()
+            .and_then(|p| std::fs::read(p).ok())
+            .and_then(|bytes| match serde_json::from_slice::<IndexFile>(&bytes) {
+                Ok(file) if file.v == FILE_VERSION => Some(file.otps),
+                Ok(file) => {
+                    log::warn!("ignoring biometry OTP index version {}", file.v);
+                    None
+                }
+                Err(e) => {
+                    log::warn!("ignoring unreadable biometry OTP index: {e}");
+                    None
+                }
+            })
+            .unwrap_or_default()
+            .into_iter()
+            .map(|o| ((o.domain.clone(), o.name.clone()), o))
+            .collect();
+
+        Self {
+            path,
+            otps: Mutex::new(otps),
+            writers: ItemLocks::default(),
+        }
+    }
+
+    fn writer(&self, domain: &str, name: &str) -> ItemGuard<'_> {
+        self.writers.lock(domain, name)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Records> {
+        self.otps.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn get(&self, domain: &str, name: &str) -> Option<OtpInfo> {
+        self.lock()
+            .get(&(domain.to_string(), name.to_string()))
+            .cloned()
+    }
+
+    #[allow(clippy::significant_drop_tightening)]
+    fn insert(&self, info: OtpInfo) -> crate::Result<()> {
+        let mut otps = self.lock();
+        otps.insert((info.domain.clone(), info.name.clone()), info);
+        self.persist(&otps)
+    }
+
+    #[allow(clippy::significant_drop_tightening)]
+    fn remove(&self, domain: &str, name: &str) -> crate::Result<()> {
+        let mut otps = self.lock();
+        if otps
+            .remove(&(domain.to_string(), name.to_string()))
+            .is_none()
+        {
+            return Ok(());
+        }
+        self.persist(&otps)
+    }
+
+    // Returns the counter to use and persists its successor. The in-memory
+    // value only moves once the write succeeded.
+    #[allow(clippy::significant_drop_tightening)]
+    fn take_counter(&self, domain: &str, name: &str) -> crate::Result<u64> {
+        let mut otps = self.lock();
+        let key = (domain.to_string(), name.to_string());
+        let counter = otps
+            .get(&key)
+            .and_then(|o| o.counter)
+            .ok_or_else(not_found)?;
+        let next = counter
+            .checked_add(1)
+            .ok_or_else(|| invalid("HOTP counter is exhausted"))?;
+        let mut updated = otps.clone();
+        if let Some(o) = updated.get_mut(&key) {
+            o.counter = Some(next);
+        }
+        self.persist(&updated)?;
+        *otps = updated;
+        Ok(counter)
+    }
+
+    fn persist(&self, otps: &Records) -> crate::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let file = IndexFile {
+            v: FILE_VERSION,
+            otps: otps.values().cloned().collect(),
+        };
+        let bytes = serde_json::to_vec(&file).map_err(std::io::Error::other)?;
+        metadata::write_atomically(path, &bytes)?;
+        Ok(())
+    }
+}
+
+/// Parses an `otpauth://` URI and seals its seed. Fails with `conflict` if
+/// an OTP with this `(domain, name)` exists, unless `overwrite` is set.
+pub fn import<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: &ImportOtpOptions,
+) -> crate::Result<OtpInfo> {
+    secret::check_supported()?;
+    if options.domain.is_empty() || options.name.is_empty() {
+        return Err(invalid("Domain and name must not be empty"));
+    }
+    let (mut info, seed) = parse_uri(&options.uri)?;
+    info.domain.clone_from(&options.domain);
+    info.name.clone_from(&options.name);
+
+    let store = app.state::<Store>();
+    let _writer = store.writer(&options.domain, &options.name);
+    let item = item_name(&options.name);
+    if options.overwrite != Some(true)
+        && (store.get(&options.domain, &options.name).is_some()
+            || secret::exists(app, &options.domain, &item)?)
+    {
+        return Err(crate::Error::rejected(
+            ErrorCode::Conflict,
+            "OTP already exists",
+        ));
+    }
+
+    let sealed = serde_json::to_string(&SealedSeed {
+        v: SEED_VERSION,
+        seed,
+    })
+    .map(SecretString::new)
+    .map_err(std::io::Error::other)?;
+    secret::store(app, window, &options.domain, &item, sealed)?;
+    if let Err(e) = store.insert(info.clone()) {
+        // Without its index entry the seed could never be used.
+        let _ = secret::remove(app, &options.domain, &item);
+        return Err(e);
+    }
+    Ok(info)
+}
+
+/// Unseals the seed — prompting for user verification — and returns the
+/// current code. HOTP advances the stored counter first.
+pub fn generate<R: Runtime>(
+    app: &AppHandle<R>,
+    window: WebviewWindow<R>,
+    options: GenerateOtpOptions,
+) -> crate::Result<OtpCode> {
+    secret::check_supported()?;
+    let store = app.state::<Store>();
+    let info = store
+        .get(&options.domain, &options.name)
+        .ok_or_else(not_found)?;
+
+    let sealed = secret::load(
+        app,
+        window,
+        &info.domain,
+        &item_name(&info.name),
+        options.reason,
+        options.cancel_title,
+    )?;
+    let sealed: SealedSeed = serde_json::from_str(&sealed).map_err(|e| {
+        crate::Error::rejected(ErrorCode::InternalError, format!("sealed seed: {e}"))
+    })?;
+    if sealed.v != SEED_VERSION {
+        return Err(crate::Error::rejected(
+            ErrorCode::InternalError,
+            "Sealed seed has an unknown version",
+        ));
+    }
+
+    match info.kind {
+        OtpKind::Totp => {
+            let period = info.period.unwrap_or(DEFAULT_PERIOD);
+            let now = metadata::now_ms() / 1000;
+            let step = now / period;
+            Ok(OtpCode {
+                code: hotp(info.algorithm, &sealed.seed, step, info.digits)?,
+                expires_at: Some((step + 1) * period * 1000),
+                counter: None,
+            })
+        }
+        OtpKind::Hotp => {
+            let counter = {
+                let _writer = store.writer(&info.domain, &info.name);
+                store.take_counter(&info.domain, &info.name)?
+            };
+            Ok(OtpCode {
+                code: hotp(info.algorithm, &sealed.seed, counter, info.digits)?,
+                expires_at: None,
+                counter: Some(counter),
+            })
+        }
+    }
+}
+
+/// Deletes the sealed seed and the index entry. Succeeds if the OTP doesn't
+/// exist, like `remove_data`.
+pub fn delete<R: Runtime>(app: &AppHandle<R>, domain: &str, name: &str) -> crate::Result<()> {
+    secret::check_supported()?;
+    let store = app.state::<Store>();
+    let _writer = store.writer(domain, name);
+    secret::remove(app, domain, &item_name(name))?;
+    store.remove(domain, name)
+}
+
+// `otpauth://TYPE/[ISSUER:]ACCOUNT?secret=BASE32[&issuer=..][&algorithm=..]
+// [&digits=..][&period=..][&counter=..]`, as written by Google
+// Authenticator's key URI format. The returned info has empty
+// `domain` / `name` for the caller to fill in.
+fn parse_uri(uri: &str) -> crate::Result<(OtpInfo, SecretBytes)> {
+    let url = Url::parse(uri).map_err(|e| invalid(&format!("malformed otpauth URI: {e}")))?;
+    if url.scheme() != "otpauth" {
+        return Err(invalid("URI scheme must be otpauth"));
+    }
+    let kind = match url.host_str() {
+        Some(host) if host.eq_ignore_ascii_case("totp") => OtpKind::Totp,
+        Some(host) if host.eq_ignore_ascii_case("hotp") => OtpKind::Hotp,
+        _ => return Err(invalid("otpauth type must be totp or hotp")),
+    };
+
+    let label = percent_decode_str(url.path().trim_start_matches('/'))
+        .decode_utf8()
+        .map_err(|_| invalid("otpauth label is not UTF-8"))?;
+    let (label_issuer, account) = match label.split_once(':') {
+        Some((issuer, account)) => (Some(issuer.trim().to_string()), account.trim()),
+        None => (None, label.trim()),
+    };
+
+    let mut seed = None;
+    let mut issuer = None;
+    let mut algorithm = OtpAlgorithm::Sha1;
+    let mut digits = DEFAULT_DIGITS;
+    let mut period = DEFAULT_PERIOD;
+    let mut counter = None;
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "secret" => {
+                // A percent-decoded value is a fresh copy of the secret;
+                // keep it where it gets wiped.
+                let value = Zeroizing::new(value.into_owned());
+                seed = Some(
+                    base32_decode(&value)
+                        .map(SecretBytes::new)
+                        .ok_or_else(|| invalid("secret is not base32"))?,
+                );
+            }
+            "issuer" => issuer = Some(value.trim().to_string()),
+            "algorithm" => {
+                algorithm = match value.to_ascii_uppercase().as_str() {
+                    "SHA1" => OtpAlgorithm::Sha1,
+                    "SHA256" => OtpAlgorithm::Sha256,
+                    "SHA512" => OtpAlgorithm::Sha512,
+                    _ => return Err(invalid("algorithm must be SHA1, SHA256 or SHA512")),
+                };
+            }
+            "digits" => {
+                digits = value
+                    .parse()
+                    .ok()
+                    .filter(|d| (MIN_DIGITS..=MAX_DIGITS).contains(d))
+                    .ok_or_else(|| invalid("digits must be 6, 7 or 8"))?;
+            }
+            "period" => {
+                period = value
+                    .parse()
+                    .ok()
+                    .filter(|p| (1..=MAX_PERIOD).contains(p))
+                    .ok_or_else(|| invalid("period must be 1 to 86400 seconds"))?;
+            }
+            "counter" => {
+                counter = Some(
+                    value
+                        .parse()
+                        .map_err(|_| invalid("counter must be a non-negative integer"))?,
+                );
+            }
+            // Unknown parameters (e.g. `image`) are ignored, as
+            // authenticator apps do.
+            _ => {}
+        }
+    }
+
+    let seed = seed.ok_or_else(|| invalid("otpauth URI has no secret"))?;
+    if seed.is_empty() || seed.len() > MAX_SEED_LEN {
+        return Err(invalid("secret must be 1 to 128 bytes"));
+    }
+    let (period, counter) = match kind {
+        OtpKind::Totp => (Some(period), None),
+        OtpKind::Hotp => (
+            None,
+            Some(counter.ok_or_else(|| invalid("hotp URI has no counter"))?),
+        ),
+    };
+
+    let info = OtpInfo {
+        domain: String::new(),
+        name: String::new(),
+        kind,
+        algorithm,
+        digits,
+        period,
+        counter,
+        issuer: issuer.or(label_issuer).filter(|i| !i.is_empty()),
+        account: Some(account.to_string()).filter(|a| !a.is_empty()),
+        created_at: metadata::now_ms(),
+    };
+    // The parsed URL holds its own copy of the secret.
+    drop(label);
+    drop(Zeroizing::new(String::from(url)));
+    Ok((info, seed))
+}
+
+// RFC 4226 section 5.3: HMAC the big-endian counter, dynamically truncate
+// to 31 bits and keep the low `digits` decimal digits. TOTP is this with
+// the time step as the counter.
+fn hotp(algorithm: OtpAlgorithm, seed: &[u8], counter: u64, digits: u32) -> crate::Result<String> {
+    let message = counter.to_be_bytes();
+    let mac = match algorithm {
+        OtpAlgorithm::Sha1 => hmac::<Hmac<sha1::Sha1>>(seed, &message),
+        OtpAlgorithm::Sha256 => hmac::<Hmac<sha2::Sha256>>(seed, &message),
+        OtpAlgorithm::Sha512 => hmac::<Hmac<sha2::Sha512>>(seed, &message),
+    }?;
+    let offset = usize::from(mac[mac.len() - 1] & 0x0f);
+    let binary = u32::from_be_bytes([
+        mac[offset],
+        mac[offset + 1],
+        mac[offset + 2],
+        mac[offset + 3],
+    ]) & 0x7fff_ffff;
+    let width = digits as usize;
+    Ok(format!("{:0width$}", binary % 10u32.pow(digits)))
+}
+
+fn hmac<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> crate::Result<Vec<u8>> {
+    let mut mac = <M as KeyInit>::new_from_slice(key)
+        .map_err(|e| crate::Error::rejected(ErrorCode::InternalError, format!("hmac: {e}")))?;
+    mac.update(message);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+// RFC 4648 base32, case-insensitive, tolerating the spaces and missing
+// padding common in hand-typed and QR-encoded secrets.
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in text
+        .bytes()
+        .filter(|c| *c != b' ')
+        .take_while(|c| *c != b'=')
+    {
+        let value = match c.to_ascii_uppercase() {
+            c @ b'A'..=b'Z' => c - b'A',
+            c @ b'2'..=b'7' => c - b'2' + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | u32::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push(u8::try_from(buffer >> bits).ok()?);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+// The reserved item an OTP's seed is sealed under.
+fn item_name(name: &str) -> String {
+    format!("{RESERVED_PREFIX}{name}")
+}
+
+fn not_found() -> crate::Error {
+    crate::Error::rejected(ErrorCode::ItemNotFound, "OTP not found")
+}
+
+fn invalid(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RFC_SEED_SHA1: &[u8] = b"12345678901234567890";
+    const RFC_SEED_SHA256: &[u8] = b"12345678901234567890123456789012";
+    const RFC_SEED_SHA512: &[u8] =
+        b"1234567890123456789012345678901234567890123456789012345678901234";
+
+    fn rejects(uri: &str) {
+        let err = parse_uri(uri).err().expect("parsed");
+        assert_eq!(err.code(), ErrorCode::InvalidInput, "{uri}");
+    }
+
+    #[test]
+    fn hotp_matches_rfc4226_appendix_d() {
+        let expected = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, code) in (0..).zip(expected) {
+            assert_eq!(
+                hotp(OtpAlgorithm::Sha1, RFC_SEED_SHA1, counter, 6).expect("hotp"),
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn totp_matches_rfc6238_appendix_b() {
+        let vectors: [(u64, [&str; 3]); 6] = [
+            (59, ["94287082", "46119246", "90693936"]),
+            (1_111_111_109, ["07081804", "68084774", "25091201"]),
+            (1_111_111_111, ["14050471", "67062674", "99943326"]),
+            (1_234_567_890, ["89005924", "91819424", "93441116"]),
+            (2_000_000_000, ["69279037", "90698825", "38618901"]),
+            (20_000_000_000, ["65353130", "77737706", "47863826"]),
+        ];
+        let algorithms = [
+            (OtpAlgorithm::Sha1, RFC_SEED_SHA1),
+            (OtpAlgorithm::Sha256, RFC_SEED_SHA256),
+            (OtpAlgorithm::Sha512, RFC_SEED_SHA512),
+        ];
+        for (time, codes) in vectors {
+            for ((algorithm, seed), code) in algorithms.iter().zip(codes) {
+                let step = time / DEFAULT_PERIOD;
+                assert_eq!(
+                    hotp(*algorithm, seed, step, 8).expect("totp"),
+                    code,
+                    "{algorithm:?} at {time}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn base32_is_lenient_about_case_spaces_and_padding() {
+        assert_eq!(base32_decode("MZXW6===").as_deref(), Some(&b"foo"[..]));
+        assert_eq!(base32_decode("MZXW6").as_deref(), Some(&b"foo"[..]));
+        assert_eq!(base32_decode("mzxw6").as_deref(), Some(&b"foo"[..]));
+        assert_eq!(base32_decode("MZXW 6").as_deref(), Some(&b"foo"[..]));
+        assert_eq!(
+            base32_decode("JBSWY3DPEHPK3PXP").as_deref(),
+            Some(&b"Hello!\xde\xad\xbe\xef"[..])
+        );
+        assert_eq!(base32_decode("").as_deref(), Some(&b""[..]));
+    }
+
+    #[test]
+    fn base32_rejects_characters_outside_the_alphabet() {
+        for text in ["MZXW1", "MZXW0", "MZXW8", "MZXW6!", "MZ-XW6"] {
+            assert_eq!(base32_decode(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn parses_otpauth_uris() {
+        let (info, seed) = parse_uri(
+            "otpauth://totp/ACME%20Co:john@example.com?secret=jbswy3dpehpk3pxp\
+             &issuer=ACME%20Co&algorithm=sha256&digits=8&period=60&image=x",
+        )
+        .expect("totp");
+        assert_eq!(&*seed, b"Hello!\xde\xad\xbe\xef");
+        assert_eq!(info.kind, OtpKind::Totp);
+        assert_eq!(info.algorithm, OtpAlgorithm::Sha256);
+        assert_eq!(info.digits, 8);
+        assert_eq!(info.period, Some(60));
+        assert_eq!(info.counter, None);
+        assert_eq!(info.issuer.as_deref(), Some("ACME Co"));
+        assert_eq!(info.account.as_deref(), Some("john@example.com"));
+
+        let (info, _) =
+            parse_uri("otpauth://hotp/Example:alice?secret=MZXW6&counter=42").expect("hotp");
+        assert_eq!(info.kind, OtpKind::Hotp);
+        assert_eq!(info.algorithm, OtpAlgorithm::Sha1);
+        assert_eq!(info.digits, DEFAULT_DIGITS);
+        assert_eq!(info.period, None);
+        assert_eq!(info.counter, Some(42));
+        // The label's issuer is the fallback for a missing `issuer`.
+        assert_eq!(info.issuer.as_deref(), Some("Example"));
+        assert_eq!(info.account.as_deref(), Some("alice"));
+    }
+
+    #[test]
+    fn hotp_counters_reach_the_disk_before_they_are_used() {
+        let dir = std::env::temp_dir().join(format!(
+            "tauri-plugin-biometry-otp-counter-{}",
+            std::process::id()
+        ));
+        let path = dir.join(FILE_NAME);
+        let store = Store::open(Some(path.clone()));
+        let (mut info, _) =
+            parse_uri("otpauth://hotp/Example:alice?secret=MZXW6&counter=41").expect("hotp");
+        info.domain = "d".to_string();
+        info.name = "n".to_string();
+        store.insert(info).expect("insert");
+
+        assert_eq!(store.take_counter("d", "n").expect("take"), 41);
+        let reloaded = Store::open(Some(path.clone()));
+        assert_eq!(reloaded.get("d", "n").and_then(|o| o.counter), Some(42));
+
+        // A counter whose successor can't be written is never handed out.
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::write(&dir, b"not a directory").expect("block dir");
+        store.take_counter("d", "n").expect_err("took");
+        assert_eq!(store.get("d", "n").and_then(|o| o.counter), Some(42));
+        let _ = std::fs::remove_file(&dir);
+    }
+
+    #[test]
+    fn rejects_invalid_otpauth_uris() {
+        // No secret, or one that isn't base32 or decodes to nothing.
+        rejects("otpauth://totp/alice");
+        rejects("otpauth://totp/alice?secret=MZXW1");
+        rejects("otpauth://totp/alice?secret=");
+        // Percent-encoding is decoded before base32.
+        rejects("otpauth://totp/alice?secret=MZXW%31");
+        // Digits out of range.
+        rejects("otpauth://totp/alice?secret=MZXW6&digits=5");
+        rejects("otpauth://totp/alice?secret=MZXW6&digits=9");
+        rejects("otpauth://totp/alice?secret=MZXW6&digits=six");
+        // Unknown algorithm.
+        rejects("otpauth://totp/alice?secret=MZXW6&algorithm=MD5");
+        // Period out of range.
+        rejects("otpauth://totp/alice?secret=MZXW6&period=0");
+        // Wrong scheme or type, and HOTP without a counter.
+        rejects("https://totp/alice?secret=MZXW6");
+        rejects("otpauth://motp/alice?secret=MZXW6");
+        rejects("otpauth://hotp/alice?secret=MZXW6");
+    }
+}
@@ -0,0 +1,158 @@
+//! What the backends hand to their platform store: the caller's data behind
+//! a header of plugin-owned flags, added by each backend's `set_data` and
+//! stripped again by its `get_data`.
+//!
+//! Each flag is a `\0biometry:<tag>\0` prefix. The flags are protected by
+//! the same user verification as the data, so unlike the metadata index
+//! they can't be lost or edited on their own:
+//!
+//! - `rust-only` marks items written with `SetDataOptions::rust_only`.
+//! - `expires-at=<ms>` carries the expiry, so a TTL still holds when the
+//!   index is gone.
+//!
+//! Payloads written without any flag are returned unchanged.
+
+// The Linux backend has no storage; the encoding is still tested there.
+#![cfg(any(
+    test,
+    not(all(desktop, not(target_os = "windows"), not(target_os = "macos")))
+))]
+
+use crate::models::{DataResponse, SetDataOptions};
+use crate::sensitive::SecretString;
+
+// NUL never appears in the text payloads apps store, so no existing item
+// starts with this by accident.
+const FLAG_START: &str = "\u{0}biometry:";
+const FLAG_END: char = '\u{0}';
+const RUST_ONLY: &str = "rust-only";
+const EXPIRES_AT: &str = "expires-at=";
+
+/// The payload to hand to the platform store for `options`. Expects a
+/// TTL to have been resolved into `expires_at` already.
+pub fn stored(options: &SetDataOptions) -> SecretString {
+    let mut flags = String::new();
+    if let Some(expires_at) = options.expires_at {
+        flags.push_str(FLAG_START);
+        flags.push_str(EXPIRES_AT);
+        flags.push_str(&expires_at.to_string());
+        flags.push(FLAG_END);
+    }
+    if options.rust_only == Some(true) {
+        flags.push_str(FLAG_START);
+        flags.push_str(RUST_ONLY);
+        flags.push(FLAG_END);
+    }
+    if flags.is_empty() {
+        return options.data.clone();
+    }
+    SecretString::new(flags + &options.data)
+}
+
+/// Builds a `get_data` response from a stored payload, moving the flags
+/// into [`DataResponse::rust_only`] and [`DataResponse::expires_at`].
+pub fn response(domain: String, name: String, stored: SecretString) -> DataResponse {
+    let mut rust_only = false;
+    let mut expires_at = None;
+    let mut rest: &str = &stored;
+    while let Some(flags) = rest.strip_prefix(FLAG_START) {
+        let Some((flag, after)) = flags.split_once(FLAG_END) else {
+            break;
+        };
+        if flag == RUST_ONLY {
+            rust_only = true;
+        } else if let Some(Ok(t)) = flag.strip_prefix(EXPIRES_AT).map(str::parse) {
+            expires_at = Some(t);
+        } else {
+            // Not one of ours: leave the rest as data.
+            break;
+        }
+        rest = after;
+    }
+    let data = if rest.len() == stored.len() {
+        stored
+    } else {
+        rest.into()
+    };
+    DataResponse {
+        domain,
+        name,
+        data,
+        rust_only,
+        expires_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(data: &str, rust_only: Option<bool>, expires_at: Option<u64>) -> SetDataOptions {
+        SetDataOptions {
+            domain: "com.example.app".to_string(),
+            name: "token".to_string(),
+            data: data.into(),
+            rust_only,
+            expires_at,
+            ..SetDataOptions::default()
+        }
+    }
+
+    fn read(stored: &str) -> DataResponse {
+        response(
+            "com.example.app".to_string(),
+            "token".to_string(),
+            stored.into(),
+        )
+    }
+
+    #[test]
+    fn flags_are_prefixed_and_stripped() {
+        let payload = stored(&options("s3cret", Some(true), Some(1_700_000_000_000)));
+        assert_eq!(
+            &*payload,
+            "\0biometry:expires-at=1700000000000\0\0biometry:rust-only\0s3cret"
+        );
+        let response = read(&payload);
+        assert_eq!(&*response.data, "s3cret");
+        assert!(response.rust_only);
+        assert_eq!(response.expires_at, Some(1_700_000_000_000));
+
+        let response = read(&stored(&options("s3cret", Some(true), None)));
+        assert_eq!(&*response.data, "s3cret");
+        assert!(response.rust_only);
+        assert_eq!(response.expires_at, None);
+    }
+
+    #[test]
+    fn unflagged_payloads_are_unchanged() {
+        for rust_only in [None, Some(false)] {
+            assert_eq!(&*stored(&options("s3cret", rust_only, None)), "s3cret");
+        }
+        // Items written before the flags existed.
+        for legacy in ["s3cret", "", "biometry:rust-only", "\0"] {
+            let response = read(legacy);
+            assert_eq!(&*response.data, legacy);
+            assert!(!response.rust_only);
+            assert_eq!(response.expires_at, None);
+        }
+    }
+
+    #[test]
+    fn unknown_or_malformed_flags_are_left_as_data() {
+        for data in [
+            "\0biometry:other\0s3cret",
+            "\0biometry:rust-only",
+            "\0biometry:expires-at=soon\0s3cret",
+        ] {
+            let response = read(data);
+            assert_eq!(&*response.data, data);
+            assert!(!response.rust_only);
+            assert_eq!(response.expires_at, None);
+        }
+        // Parsing stops at the first flag it doesn't know.
+        let response = read("\0biometry:rust-only\0\0biometry:other\0s3cret");
+        assert!(response.rust_only);
+        assert_eq!(&*response.data, "\0biometry:other\0s3cret");
+    }
+}
@@ -0,0 +1,54 @@
+//! Items written with `SetDataOptions::rust_only`, which the `get_data`
+//! command refuses while `app.biometry().get_data(..)` still returns them.
+//!
+//! The flag is carried inside the protected payload (see [`crate::payload`]).
+//! Unlike the metadata index it can't be lost or edited without the secret
+//! itself. The index keeps a copy only so the command can refuse before
+//! prompting.
+
+use crate::error::ErrorCode;
+use crate::models::DataResponse;
+
+/// The error the `get_data` command returns for a Rust-only item.
+pub fn denied() -> crate::Error {
+    crate::Error::rejected(ErrorCode::RustOnly, "Item can only be read from Rust")
+}
+
+/// What the `get_data` command may pass on, as data or as a handle, and
+/// `fetch_with_secret` may send without a scope opt-in: the response
+/// itself, or `rustOnly` if the payload was flagged.
+pub fn for_webview(response: DataResponse) -> crate::Result<DataResponse> {
+    if response.rust_only {
+        return Err(denied());
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SetDataOptions;
+    use crate::payload;
+
+    fn read_back(rust_only: Option<bool>) -> DataResponse {
+        let stored = payload::stored(&SetDataOptions {
+            domain: "com.example.app".to_string(),
+            name: "token".to_string(),
+            data: "s3cret".into(),
+            rust_only,
+            ..SetDataOptions::default()
+        });
+        payload::response("com.example.app".to_string(), "token".to_string(), stored)
+    }
+
+    #[test]
+    fn flagged_payloads_are_refused_to_the_webview() {
+        let err = for_webview(read_back(Some(true))).expect_err("returned");
+        assert_eq!(err.code(), ErrorCode::RustOnly);
+
+        for rust_only in [None, Some(false)] {
+            let response = for_webview(read_back(rust_only)).expect("response");
+            assert_eq!(&*response.data, "s3cret");
+        }
+    }
+}
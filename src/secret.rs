@@ -0,0 +1,345 @@
+//! Secrets the plugin keeps for itself — private keys, `derive_key` roots,
+//! OTP seeds — stored as ordinary items under reserved names, so reading
+//! one goes through the same user verification as `get_data` while the data
+//! commands can never reach it.
+//!
+//! Linux has no platform store. There, the key, `derive_key` and OTP APIs
+//! fail with `unsupportedPlatform` unless the `insecure-software-store`
+//! feature is on, which keeps the secrets in a plaintext file in the app's
+//! local data directory instead. That store has no hardware binding and no
+//! user verification; it exists so the API can be exercised in development
+//! and tests, and is always used by the crate's own tests.
+
+use crate::error::ErrorCode;
+use crate::{keys, otp};
+
+/// Item name prefixes owned by the plugin.
+const RESERVED_PREFIXES: [&str; 2] = [keys::RESERVED_PREFIX, otp::RESERVED_PREFIX];
+
+/// Rejects item names in the ranges reserved for the plugin's own secrets,
+/// so the data commands can neither read nor overwrite them.
+pub fn check_not_reserved(name: &str) -> crate::Result<()> {
+    if let Some(prefix) = RESERVED_PREFIXES.iter().find(|p| name.starts_with(*p)) {
+        return Err(crate::Error::rejected(
+            ErrorCode::InvalidInput,
+            format!("Item names starting with \"{prefix}\" are reserved"),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the plugin has somewhere to keep its secrets.
+pub const SUPPORTED: bool = !cfg!(all(
+    desktop,
+    not(target_os = "windows"),
+    not(target_os = "macos")
+)) || cfg!(any(test, feature = "insecure-software-store"));
+
+/// Fails with `unsupportedPlatform` unless [`SUPPORTED`]. Called first by
+/// every key, `derive_key` and OTP operation.
+pub fn check_supported() -> crate::Result<()> {
+    if SUPPORTED {
+        return Ok(());
+    }
+    Err(crate::Error::rejected(
+        ErrorCode::UnsupportedPlatform,
+        "Keys need the insecure-software-store feature on this platform",
+    ))
+}
+
+#[cfg(not(all(desktop, not(target_os = "windows"), not(target_os = "macos"))))]
+pub use platform::{exists, load, remove, store};
+#[cfg(all(
+    desktop,
+    not(target_os = "windows"),
+    not(target_os = "macos"),
+    any(test, feature = "insecure-software-store")
+))]
+pub use software::{exists, load, remove, store, Software};
+#[cfg(all(
+    desktop,
+    not(target_os = "windows"),
+    not(target_os = "macos"),
+    not(any(test, feature = "insecure-software-store"))
+))]
+pub use unsupported::{exists, load, remove, store};
+
+// The platform store, everywhere it exists. `item` is the full reserved
+// item name.
+#[cfg(not(all(desktop, not(target_os = "windows"), not(target_os = "macos"))))]
+mod platform {
+    use tauri::{AppHandle, Runtime, WebviewWindow};
+
+    use crate::models::{DataOptions, GetDataOptions, SetDataOptions};
+    use crate::sensitive::SecretString;
+    use crate::BiometryExt;
+
+    pub fn exists<R: Runtime>(app: &AppHandle<R>, domain: &str, item: &str) -> crate::Result<bool> {
+        app.biometry().backend_has_data(DataOptions {
+            domain: domain.to_string(),
+            name: item.to_string(),
+        })
+    }
+
+    pub fn store<R: Runtime>(
+        app: &AppHandle<R>,
+        window: WebviewWindow<R>,
+        domain: &str,
+        item: &str,
+        sealed: SecretString,
+    ) -> crate::Result<()> {
+        app.biometry().backend_set_data(
+            window,
+            SetDataOptions {
+                domain: domain.to_string(),
+                name: item.to_string(),
+                data: sealed,
+                ..SetDataOptions::default()
+            },
+        )
+    }
+
+    pub fn load<R: Runtime>(
+        app: &AppHandle<R>,
+        window: WebviewWindow<R>,
+        domain: &str,
+        item: &str,
+        reason: String,
+        cancel_title: Option<String>,
+    ) -> crate::Result<SecretString> {
+        app.biometry()
+            .backend_get_data(
+                window,
+                GetDataOptions {
+                    domain: domain.to_string(),
+                    name: item.to_string(),
+                    reason,
+                    cancel_title,
+                    handle: None,
+                },
+            )
+            .map(|response| response.data)
+    }
+
+    pub fn remove<R: Runtime>(app: &AppHandle<R>, domain: &str, item: &str) -> crate::Result<()> {
+        app.biometry().backend_remove_data(DataOptions {
+            domain: domain.to_string(),
+            name: item.to_string(),
+        })
+    }
+}
+
+#[cfg(all(
+    desktop,
+    not(target_os = "windows"),
+    not(target_os = "macos"),
+    any(test, feature = "insecure-software-store")
+))]
+mod software {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::sync::{Mutex, MutexGuard, PoisonError};
+
+    use serde::{Deserialize, Serialize};
+    use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+    use zeroize::Zeroizing;
+
+    use crate::error::ErrorCode;
+    use crate::metadata;
+    use crate::sensitive::SecretString;
+
+    const FILE_NAME: &str = "biometry-software-keys.json";
+    const FILE_VERSION: u8 = 1;
+
+    type Entries = BTreeMap<(String, String), SecretString>;
+
+    #[derive(Serialize, Deserialize)]
+    struct Entry {
+        domain: String,
+        name: String,
+        sealed: SecretString,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct SecretsFile {
+        v: u8,
+        keys: Vec<Entry>,
+    }
+
+    /// Unprotected secret store, written owner-only. Managed as plugin state
+    /// on Linux.
+    pub struct Software {
+        path: Option<PathBuf>,
+        entries: Mutex<Entries>,
+    }
+
+    impl Software {
+        pub fn load<R: Runtime>(app: &AppHandle<R>) -> Self {
+            let path = app
+                .path()
+                .app_local_data_dir()
+                .ok()
+                .map(|dir| dir.join(FILE_NAME));
+            let entries = path
+                .as_ref()
+                .and_then(|p| std::fs::read(p).ok())
+                .and_then(|bytes| serde_json::from_slice::<SecretsFile>(&bytes).ok())
+                .filter(|file| file.v == FILE_VERSION)
+                .map(|file| file.keys)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|e| ((e.domain, e.name), e.sealed))
+                .collect();
+            Self {
+                path,
+                entries: Mutex::new(entries),
+            }
+        }
+
+        /// An empty store that is never written to disk.
+        #[cfg(test)]
+        pub fn in_memory() -> Self {
+            Self {
+                path: None,
+                entries: Mutex::default(),
+            }
+        }
+
+        fn lock(&self) -> MutexGuard<'_, Entries> {
+            self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+        }
+
+        fn get(&self, domain: &str, item: &str) -> Option<SecretString> {
+            self.lock()
+                .get(&(domain.to_string(), item.to_string()))
+                .cloned()
+        }
+
+        #[allow(clippy::significant_drop_tightening)]
+        fn insert(&self, domain: &str, item: &str, sealed: SecretString) -> crate::Result<()> {
+            let mut entries = self.lock();
+            entries.insert((domain.to_string(), item.to_string()), sealed);
+            self.persist(&entries)
+        }
+
+        #[allow(clippy::significant_drop_tightening)]
+        fn remove(&self, domain: &str, item: &str) -> crate::Result<()> {
+            let mut entries = self.lock();
+            if entries
+                .remove(&(domain.to_string(), item.to_string()))
+                .is_none()
+            {
+                return Ok(());
+            }
+            self.persist(&entries)
+        }
+
+        fn persist(&self, entries: &Entries) -> crate::Result<()> {
+            let Some(path) = &self.path else {
+                return Ok(());
+            };
+            let file = SecretsFile {
+                v: FILE_VERSION,
+                keys: entries
+                    .iter()
+                    .map(|((domain, name), sealed)| Entry {
+                        domain: domain.clone(),
+                        name: name.clone(),
+                        sealed: sealed.clone(),
+                    })
+                    .collect(),
+            };
+            let bytes = Zeroizing::new(serde_json::to_vec(&file).map_err(std::io::Error::other)?);
+            metadata::write_atomically(path, &bytes)?;
+            Ok(())
+        }
+    }
+
+    // Signatures mirror the platform-store variant.
+    #[allow(clippy::unnecessary_wraps)]
+    pub fn exists<R: Runtime>(app: &AppHandle<R>, domain: &str, item: &str) -> crate::Result<bool> {
+        Ok(app.state::<Software>().get(domain, item).is_some())
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn store<R: Runtime>(
+        app: &AppHandle<R>,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        item: &str,
+        sealed: SecretString,
+    ) -> crate::Result<()> {
+        app.state::<Software>().insert(domain, item, sealed)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn load<R: Runtime>(
+        app: &AppHandle<R>,
+        _window: WebviewWindow<R>,
+        domain: &str,
+        item: &str,
+        _reason: String,
+        _cancel_title: Option<String>,
+    ) -> crate::Result<SecretString> {
+        app.state::<Software>()
+            .get(domain, item)
+            .ok_or_else(|| crate::Error::rejected(ErrorCode::ItemNotFound, "Item not found"))
+    }
+
+    pub fn remove<R: Runtime>(app: &AppHandle<R>, domain: &str, item: &str) -> crate::Result<()> {
+        app.state::<Software>().remove(domain, item)
+    }
+}
+
+// Linux without the software store. Unreachable through the plugin's API,
+// which calls `check_supported` first.
+#[cfg(all(
+    desktop,
+    not(target_os = "windows"),
+    not(target_os = "macos"),
+    not(any(test, feature = "insecure-software-store"))
+))]
+mod unsupported {
+    use tauri::{AppHandle, Runtime, WebviewWindow};
+
+    use crate::sensitive::SecretString;
+
+    pub fn exists<R: Runtime>(
+        _app: &AppHandle<R>,
+        _domain: &str,
+        _item: &str,
+    ) -> crate::Result<bool> {
+        super::check_supported().map(|()| false)
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn store<R: Runtime>(
+        _app: &AppHandle<R>,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _item: &str,
+        _sealed: SecretString,
+    ) -> crate::Result<()> {
+        super::check_supported()
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn load<R: Runtime>(
+        _app: &AppHandle<R>,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _item: &str,
+        _reason: String,
+        _cancel_title: Option<String>,
+    ) -> crate::Result<SecretString> {
+        super::check_supported().map(|()| SecretString::default())
+    }
+
+    pub fn remove<R: Runtime>(
+        _app: &AppHandle<R>,
+        _domain: &str,
+        _item: &str,
+    ) -> crate::Result<()> {
+        super::check_supported()
+    }
+}
@@ -0,0 +1,166 @@
+//! Cached results of the `status` command. The platform probes can take
+//! hundreds of milliseconds (Windows runs three of them), and UIs tend to
+//! check on every navigation.
+//!
+//! A cached status is reused for `TTL`, and dropped early when a window
+//! gains focus — the user may have changed settings elsewhere — or when a
+//! prompting command fails, since that can mean the biometric state
+//! changed. `status({ fresh: true })` always probes. The Rust API's
+//! `app.biometry().status()` is not cached.
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Runtime};
+
+use crate::models::Status;
+use crate::BiometryExt;
+
+const TTL: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct State {
+    entry: Option<(Instant, Status)>,
+    // Bumped by every `invalidate`, so a probe that was already running
+    // can tell its result may predate the change and not cache it.
+    generation: u64,
+}
+
+/// The last probed status, managed as plugin state.
+#[derive(Default)]
+pub struct Cache {
+    state: Mutex<State>,
+}
+
+impl Cache {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns the cached status if it is younger than `TTL` and `fresh`
+    /// is false, and probes the backend otherwise.
+    pub fn get<R: Runtime>(&self, app: &AppHandle<R>, fresh: bool) -> crate::Result<Status> {
+        self.get_or_probe(fresh, || app.biometry().status())
+    }
+
+    fn get_or_probe(
+        &self,
+        fresh: bool,
+        probe: impl FnOnce() -> crate::Result<Status>,
+    ) -> crate::Result<Status> {
+        let generation = {
+            let state = self.lock();
+            if !fresh {
+                if let Some((at, status)) = &state.entry {
+                    if at.elapsed() < TTL {
+                        return Ok(status.clone());
+                    }
+                }
+            }
+            state.generation
+        };
+        // Probed outside the lock so a slow probe doesn't block
+        // `invalidate`; concurrent misses just probe twice.
+        let status = probe()?;
+        let mut state = self.lock();
+        if state.generation == generation {
+            state.entry = Some((Instant::now(), status.clone()));
+        }
+        drop(state);
+        Ok(status)
+    }
+
+    pub fn invalidate(&self) {
+        let mut state = self.lock();
+        state.entry = None;
+        state.generation = state.generation.wrapping_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use crate::models::{BiometryStrength, BiometryType, Capabilities};
+
+    // A status that records which probe produced it.
+    fn probed(n: u32) -> Status {
+        Status {
+            is_available: true,
+            biometry_type: BiometryType::TouchID,
+            error: None,
+            error_code: None,
+            modalities: vec![BiometryType::TouchID],
+            capabilities: Capabilities {
+                storage: true,
+                device_credential: true,
+                keys: true,
+                strength: BiometryStrength::Strong,
+                backend: "test".to_string(),
+                backend_version: Some(n.to_string()),
+            },
+        }
+    }
+
+    fn probe(cache: &Cache, fresh: bool, n: u32) -> String {
+        cache
+            .get_or_probe(fresh, || Ok(probed(n)))
+            .expect("status")
+            .capabilities
+            .backend_version
+            .expect("version")
+    }
+
+    #[test]
+    fn cached_until_ttl_or_fresh() {
+        let cache = Cache::default();
+        assert_eq!(probe(&cache, false, 1), "1");
+        assert_eq!(probe(&cache, false, 2), "1");
+        assert_eq!(probe(&cache, true, 3), "3");
+        assert_eq!(probe(&cache, false, 4), "3");
+
+        if let Some((at, _)) = &mut cache.lock().entry {
+            *at = at.checked_sub(TTL).expect("instant");
+        }
+        assert_eq!(probe(&cache, false, 5), "5");
+        assert_eq!(probe(&cache, false, 6), "5");
+    }
+
+    #[test]
+    fn invalidate_drops_the_cached_status() {
+        let cache = Cache::default();
+        assert_eq!(probe(&cache, false, 1), "1");
+        cache.invalidate();
+        assert_eq!(probe(&cache, false, 2), "2");
+        assert_eq!(probe(&cache, false, 3), "2");
+    }
+
+    #[test]
+    fn failed_probes_are_not_cached() {
+        let cache = Cache::default();
+        let err = cache
+            .get_or_probe(false, || {
+                Err(crate::Error::rejected(ErrorCode::InternalError, "probe"))
+            })
+            .expect_err("status");
+        assert_eq!(err.code(), ErrorCode::InternalError);
+        assert_eq!(probe(&cache, false, 1), "1");
+    }
+
+    #[test]
+    fn probes_overtaken_by_an_invalidation_are_not_cached() {
+        let cache = Cache::default();
+        // Invalidated while the probe runs, e.g. by a focus event: the
+        // result goes to its caller but the next call probes again.
+        let status = cache
+            .get_or_probe(false, || {
+                cache.invalidate();
+                Ok(probed(1))
+            })
+            .expect("status");
+        assert_eq!(status.capabilities.backend_version.as_deref(), Some("1"));
+        assert!(cache.lock().entry.is_none());
+        assert_eq!(probe(&cache, false, 2), "2");
+        assert_eq!(probe(&cache, false, 3), "2");
+    }
+}
@@ -0,0 +1,254 @@
+//! The storage API on [`crate::Biometry`]. Each backend only moves payloads
+//! in and out of its platform store; this layer keeps the metadata index,
+//! TTL purges and conditional writes in step with it, so items written from
+//! Rust get the same bookkeeping as items written over IPC.
+//!
+//! Expiry is enforced here rather than in the commands, from two sources:
+//! the index, which lets `has_data` answer without prompting, and the copy
+//! carried in the payload (see [`crate::payload`]), which `get_data` checks
+//! so a TTL still holds when the index is lost.
+
+use tauri::{Manager, Runtime, WebviewWindow};
+
+use crate::error::ErrorCode;
+use crate::metadata;
+use crate::models::{
+    DataOptions, DataResponse, GetDataOptions, RemoveDataOptions, RenameDataOptions, SetDataOptions,
+};
+
+// The public methods keep the by-value signatures the backends have always
+// had.
+#[allow(clippy::needless_pass_by_value)]
+impl<R: Runtime> crate::Biometry<R> {
+    /// Whether an item exists, without prompting. An item the index knows
+    /// to be expired is deleted and reported absent.
+    pub fn has_data(&self, options: DataOptions) -> crate::Result<bool> {
+        if self.purge_if_expired(&options.domain, &options.name)? {
+            return Ok(false);
+        }
+        self.backend_has_data(options)
+    }
+
+    /// Reads an item after user verification. Unlike the `get_data`
+    /// command, this returns Rust-only items. Fails with `itemNotFound`
+    /// once the item has expired, and deletes it.
+    pub fn get_data(
+        &self,
+        window: WebviewWindow<R>,
+        options: GetDataOptions,
+    ) -> crate::Result<DataResponse> {
+        if self.purge_if_expired(&options.domain, &options.name)? {
+            return Err(expired());
+        }
+        let response = self.backend_get_data(window, options)?;
+        let now = metadata::now_ms();
+        if response.expires_at.is_some_and(|t| t <= now) {
+            let store = self.app().state::<metadata::Store>();
+            let _writer = store.writer(&response.domain, &response.name);
+            // A fresh index entry means the item was rewritten since it was
+            // read; only delete what is still the expired item.
+            let rewritten = store
+                .get(&response.domain, &response.name)
+                .is_some_and(|m| m.expires_at.map_or(true, |t| t > now));
+            if !rewritten {
+                self.backend_remove_data(RemoveDataOptions {
+                    domain: response.domain.clone(),
+                    name: response.name.clone(),
+                })?;
+                store.remove(&response.domain, &response.name)?;
+            }
+            return Err(expired());
+        }
+        Ok(response)
+    }
+
+    /// Writes an item and records its metadata. Honors `if_absent` and
+    /// `if_version`, and fails with `conflict` if they don't hold.
+    pub fn set_data(&self, window: WebviewWindow<R>, options: SetDataOptions) -> crate::Result<()> {
+        metadata::validate(&options)?;
+        // The payload carries an absolute expiry.
+        let options = SetDataOptions {
+            expires_at: metadata::expiry(&options, metadata::now_ms()),
+            ttl: None,
+            ..options
+        };
+        let store = self.app().state::<metadata::Store>();
+        let _writer = store.writer(&options.domain, &options.name);
+        self.purge_expired_locked(&store, &options.domain, &options.name)?;
+        self.check_write_preconditions(&store, &options)?;
+        self.backend_set_data(window, options.clone())?;
+        store.record_write(&options)
+    }
+
+    /// Deletes an item and its metadata. Deleting a missing item succeeds.
+    pub fn remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
+        let store = self.app().state::<metadata::Store>();
+        let _writer = store.writer(&options.domain, &options.name);
+        self.backend_remove_data(options.clone())?;
+        store.remove(&options.domain, &options.name)
+    }
+
+    /// Moves an item, with its metadata, to a new `(domain, name)` without
+    /// the payload leaving the backend where it can avoid it. Fails with
+    /// `conflict` if the target exists and with `itemNotFound` if the
+    /// source has expired.
+    pub fn rename_data(
+        &self,
+        window: WebviewWindow<R>,
+        options: RenameDataOptions,
+    ) -> crate::Result<()> {
+        let store = self.app().state::<metadata::Store>();
+        let _writers = store.writers(&[
+            (&options.domain, &options.name),
+            (&options.new_domain, &options.new_name),
+        ]);
+        if self.purge_expired_locked(&store, &options.domain, &options.name)? {
+            return Err(expired());
+        }
+        // An expired target is as good as absent; clear it so the backend's
+        // conflict check doesn't trip over it.
+        self.purge_expired_locked(&store, &options.new_domain, &options.new_name)?;
+        self.backend_rename_data(window, options.clone())?;
+        store.rename(
+            &options.domain,
+            &options.name,
+            &options.new_domain,
+            &options.new_name,
+        )
+    }
+
+    /// Deletes `(domain, name)` from the backend and the metadata index if
+    /// its TTL has passed. Returns whether the item was expired.
+    pub(crate) fn purge_if_expired(&self, domain: &str, name: &str) -> crate::Result<bool> {
+        let store = self.app().state::<metadata::Store>();
+        if !store.is_expired(domain, name, metadata::now_ms()) {
+            return Ok(false);
+        }
+        let _writer = store.writer(domain, name);
+        self.purge_expired_locked(&store, domain, name)
+    }
+
+    // Same as `purge_if_expired` for callers already holding the item's
+    // `Store::writer`. Re-checks the expiry under the lock: a concurrent
+    // `set_data` may have replaced the item since the unlocked check.
+    fn purge_expired_locked(
+        &self,
+        store: &metadata::Store,
+        domain: &str,
+        name: &str,
+    ) -> crate::Result<bool> {
+        if !store.is_expired(domain, name, metadata::now_ms()) {
+            return Ok(false);
+        }
+        self.backend_remove_data(RemoveDataOptions {
+            domain: domain.to_string(),
+            name: name.to_string(),
+        })?;
+        store.remove(domain, name)?;
+        Ok(true)
+    }
+
+    // Enforces `if_absent` / `if_version`. Must run under the item's
+    // `Store::writer`.
+    // Items that exist in the backend but predate the metadata index count
+    // as revision 0.
+    fn check_write_preconditions(
+        &self,
+        store: &metadata::Store,
+        options: &SetDataOptions,
+    ) -> crate::Result<()> {
+        let if_absent = options.if_absent.unwrap_or(false);
+        if !if_absent && options.if_version.is_none() {
+            return Ok(());
+        }
+        let exists = self.backend_has_data(DataOptions {
+            domain: options.domain.clone(),
+            name: options.name.clone(),
+        })?;
+        let current = exists.then(|| {
+            store
+                .get(&options.domain, &options.name)
+                .map_or(0, |m| m.revision)
+        });
+        check_revision(options, current)
+    }
+}
+
+// `current` is the item's revision, or `None` if it doesn't exist.
+fn check_revision(options: &SetDataOptions, current: Option<u64>) -> crate::Result<()> {
+    if options.if_absent == Some(true) && current.is_some() {
+        return Err(crate::Error::rejected(
+            ErrorCode::Conflict,
+            "Item already exists",
+        ));
+    }
+    if let Some(expected) = options.if_version {
+        if current != Some(expected) {
+            return Err(crate::Error::rejected(
+                ErrorCode::Conflict,
+                format!(
+                    "Item revision is {}, expected {expected}",
+                    current.map_or_else(|| "absent".to_string(), |r| r.to_string())
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn expired() -> crate::Error {
+    crate::Error::rejected(ErrorCode::ItemNotFound, "Item has expired")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(if_absent: Option<bool>, if_version: Option<u64>) -> SetDataOptions {
+        SetDataOptions {
+            domain: "com.example.app".to_string(),
+            name: "token".to_string(),
+            if_absent,
+            if_version,
+            ..SetDataOptions::default()
+        }
+    }
+
+    fn conflicts(options: &SetDataOptions, current: Option<u64>) -> bool {
+        match check_revision(options, current) {
+            Ok(()) => false,
+            Err(e) => {
+                assert_eq!(e.code(), ErrorCode::Conflict);
+                true
+            }
+        }
+    }
+
+    #[test]
+    fn unconditional_writes_never_conflict() {
+        for current in [None, Some(0), Some(3)] {
+            assert!(!conflicts(&options(None, None), current));
+            assert!(!conflicts(&options(Some(false), None), current));
+        }
+    }
+
+    #[test]
+    fn if_absent_conflicts_with_any_existing_item() {
+        let if_absent = options(Some(true), None);
+        assert!(!conflicts(&if_absent, None));
+        // Items written before the index existed count as revision 0.
+        assert!(conflicts(&if_absent, Some(0)));
+        assert!(conflicts(&if_absent, Some(2)));
+    }
+
+    #[test]
+    fn if_version_needs_the_current_revision() {
+        let if_version = options(None, Some(2));
+        assert!(!conflicts(&if_version, Some(2)));
+        assert!(conflicts(&if_version, Some(1)));
+        assert!(conflicts(&if_version, Some(3)));
+        assert!(conflicts(&if_version, None));
+        assert!(!conflicts(&options(None, Some(0)), Some(0)));
+        assert!(conflicts(&options(Some(true), Some(0)), Some(0)));
+    }
+}
@@ -0,0 +1,460 @@
+//! Chunked file encryption for `encrypt_file` / `decrypt_file`.
+//!
+//! Files are encrypted under a random per-file data key with AES-256-GCM in
+//! the STREAM construction (Hoang et al.): 64 KiB plaintext chunks, nonce =
+//! 7-byte random prefix || 32-bit big-endian chunk counter || last-chunk
+//! flag. Reordering, dropping or truncating chunks fails authentication, and
+//! neither side ever holds more than two chunks in memory.
+//!
+//! File layout (version 1):
+//!
+//! ```text
+//! "TPBF" | version: u8 = 1 | header length: u32 BE | header JSON | chunks…
+//! ```
+//!
+//! The header is `{ "alg": "A256GCM-STREAM-BE32", "chunk": 65536,
+//! "prefix": "<base64, 7 bytes>", "dek": "<encrypt envelope>" }`, where
+//! `dek` is the data key wrapped with the `encrypt` envelope for the file's
+//! key. Everything before the first chunk is the associated data of every
+//! chunk, so the header can't be swapped onto another file's chunks.
+//!
+//! Output goes to a new, randomly named temp file next to the target that is
+//! renamed over it only once the last chunk is written (and, when
+//! decrypting, authenticated), so a failed or interrupted call never leaves
+//! a truncated file behind.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use aes_gcm::{
+    aead::{
+        stream::{DecryptorBE32, EncryptorBE32},
+        KeyInit, Payload,
+    },
+    Aes256Gcm,
+};
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::b64_field;
+use crate::error::ErrorCode;
+use crate::metadata;
+use crate::sensitive::SecretBytes;
+
+const MAGIC: &[u8; 4] = b"TPBF";
+const VERSION: u8 = 1;
+const ALG: &str = "A256GCM-STREAM-BE32";
+const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+const PREFIX_LEN: usize = 7;
+// A wrapped data key is a few hundred bytes; anything much larger isn't a
+// header this code wrote.
+const MAX_HEADER_LEN: u32 = 16 * 1024;
+
+pub const DEK_LEN: usize = 32;
+
+#[derive(Serialize, Deserialize)]
+struct Header {
+    alg: String,
+    chunk: usize,
+    #[serde(with = "b64_field")]
+    prefix: Vec<u8>,
+    dek: String,
+}
+
+/// A fresh random data key.
+pub fn new_dek() -> Zeroizing<[u8; DEK_LEN]> {
+    let mut dek = Zeroizing::new([0u8; DEK_LEN]);
+    rand::rng().fill(dek.as_mut());
+    dek
+}
+
+/// Encrypts `input` to `output` under `dek`, storing `wrapped_dek` in the
+/// header.
+pub fn encrypt_file(
+    dek: &[u8; DEK_LEN],
+    wrapped_dek: String,
+    input: &Path,
+    output: &Path,
+) -> crate::Result<()> {
+    check_distinct(input, output)?;
+    let mut reader = BufReader::new(File::open(input)?);
+    write_through_temp(output, |writer| {
+        encrypt(dek, wrapped_dek, &mut reader, writer)
+    })
+}
+
+/// Decrypts `input` to `output`. The header is read first, so a file that
+/// isn't one of ours fails before `unwrap_dek` — the step that prompts — is
+/// called with the wrapped data key.
+pub fn decrypt_file(
+    input: &Path,
+    output: &Path,
+    unwrap_dek: impl FnOnce(&str) -> crate::Result<SecretBytes>,
+) -> crate::Result<()> {
+    check_distinct(input, output)?;
+    let mut reader = BufReader::new(File::open(input)?);
+    let opened = read_header(&mut reader)?;
+    let dek = unwrap_dek(&opened.wrapped_dek)?;
+    write_through_temp(output, |writer| decrypt(&dek, &opened, &mut reader, writer))
+}
+
+fn encrypt(
+    dek: &[u8; DEK_LEN],
+    wrapped_dek: String,
+    input: &mut impl Read,
+    output: &mut impl Write,
+) -> crate::Result<()> {
+    let mut prefix = [0u8; PREFIX_LEN];
+    rand::rng().fill(&mut prefix);
+    let header = serde_json::to_vec(&Header {
+        alg: ALG.to_string(),
+        chunk: CHUNK_LEN,
+        prefix: prefix.to_vec(),
+        dek: wrapped_dek,
+    })
+    .map_err(std::io::Error::other)?;
+    let aad = preamble(&header)?;
+    output.write_all(&aad)?;
+
+    let cipher = Aes256Gcm::new_from_slice(dek).map_err(|e| internal(&format!("aes: {e}")))?;
+    let mut encryptor = EncryptorBE32::from_aead(cipher, (&prefix).into());
+    let mut current = Zeroizing::new(read_chunk(input, CHUNK_LEN)?);
+    loop {
+        let next = Zeroizing::new(read_chunk(input, CHUNK_LEN)?);
+        let payload = Payload {
+            msg: &current,
+            aad: &aad,
+        };
+        if next.is_empty() {
+            let chunk = encryptor
+                .encrypt_last(payload)
+                .map_err(|_| encrypt_failed())?;
+            output.write_all(&chunk)?;
+            break;
+        }
+        let chunk = encryptor
+            .encrypt_next(payload)
+            .map_err(|_| encrypt_failed())?;
+        output.write_all(&chunk)?;
+        current = next;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+// Reads and checks the header, leaving `input` at the first chunk.
+fn read_header(input: &mut impl Read) -> crate::Result<Opened> {
+    let mut fixed = [0u8; 9];
+    input
+        .read_exact(&mut fixed)
+        .map_err(|_| invalid("not an encrypted file"))?;
+    if &fixed[..4] != MAGIC {
+        return Err(invalid("not an encrypted file"));
+    }
+    if fixed[4] != VERSION {
+        return Err(invalid(&format!(
+            "unsupported encrypted file version {}",
+            fixed[4]
+        )));
+    }
+    let len = u32::from_be_bytes([fixed[5], fixed[6], fixed[7], fixed[8]]);
+    if len > MAX_HEADER_LEN {
+        return Err(invalid("encrypted file header is too large"));
+    }
+    let mut raw = vec![0u8; len as usize];
+    input
+        .read_exact(&mut raw)
+        .map_err(|_| invalid("encrypted file header is truncated"))?;
+    let header: Header = serde_json::from_slice(&raw)
+        .map_err(|e| invalid(&format!("malformed encrypted file header: {e}")))?;
+    if header.alg != ALG || header.chunk != CHUNK_LEN {
+        return Err(invalid("unsupported encrypted file algorithm"));
+    }
+    let prefix: [u8; PREFIX_LEN] = header
+        .prefix
+        .as_slice()
+        .try_into()
+        .map_err(|_| invalid("encrypted file nonce prefix has the wrong length"))?;
+    Ok(Opened {
+        aad: preamble(&raw)?,
+        prefix,
+        wrapped_dek: header.dek,
+    })
+}
+
+struct Opened {
+    aad: Vec<u8>,
+    prefix: [u8; PREFIX_LEN],
+    wrapped_dek: String,
+}
+
+// On error some authenticated plaintext may already have been written; the
+// caller discards the temporary file.
+fn decrypt(
+    dek: &[u8],
+    opened: &Opened,
+    input: &mut impl Read,
+    output: &mut impl Write,
+) -> crate::Result<()> {
+    let cipher = Aes256Gcm::new_from_slice(dek).map_err(|e| internal(&format!("aes: {e}")))?;
+    let mut decryptor = DecryptorBE32::from_aead(cipher, (&opened.prefix).into());
+    let mut current = read_chunk(input, CHUNK_LEN + TAG_LEN)?;
+    loop {
+        let next = read_chunk(input, CHUNK_LEN + TAG_LEN)?;
+        let payload = Payload {
+            msg: &current,
+            aad: &opened.aad,
+        };
+        if next.is_empty() {
+            let chunk = Zeroizing::new(
+                decryptor
+                    .decrypt_last(payload)
+                    .map_err(|_| decrypt_failed())?,
+            );
+            output.write_all(&chunk)?;
+            break;
+        }
+        let chunk = Zeroizing::new(
+            decryptor
+                .decrypt_next(payload)
+                .map_err(|_| decrypt_failed())?,
+        );
+        output.write_all(&chunk)?;
+        current = next;
+    }
+    output.flush()?;
+    Ok(())
+}
+
+// Runs `write` against a new temp file next to `output` (see
+// `metadata::create_temp`: random name, `create_new`, owner-only on unix,
+// since on decrypt it holds plaintext) and renames it into place on
+// success. Only that temp file is ever removed.
+fn write_through_temp(
+    output: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> crate::Result<()>,
+) -> crate::Result<()> {
+    let (tmp, file) = metadata::create_temp(output)?;
+    let result = (|| {
+        let mut writer = BufWriter::new(file);
+        write(&mut writer)?;
+        writer
+            .into_inner()
+            .map_err(std::io::IntoInnerError::into_error)?
+            .sync_all()?;
+        fs::rename(&tmp, output)?;
+        metadata::sync_dir(output)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+// Writing over the input would replace the only copy of it, so paths that
+// name the same file are refused before anything is read or prompted for.
+fn check_distinct(input: &Path, output: &Path) -> crate::Result<()> {
+    let same = input == output
+        || matches!(
+            (fs::canonicalize(input), fs::canonicalize(output)),
+            (Ok(input), Ok(output)) if input == output
+        );
+    if same {
+        return Err(invalid("input and output must be different files"));
+    }
+    Ok(())
+}
+
+// Magic, version, header length and header: the bytes before the first
+// chunk, used as every chunk's associated data.
+fn preamble(header: &[u8]) -> crate::Result<Vec<u8>> {
+    let len = u32::try_from(header.len()).map_err(|_| internal("header too large"))?;
+    let mut out = Vec::with_capacity(9 + header.len());
+    out.extend_from_slice(MAGIC);
+    out.push(VERSION);
+    out.extend_from_slice(&len.to_be_bytes());
+    out.extend_from_slice(header);
+    Ok(out)
+}
+
+// Reads up to `len` bytes, stopping short only at end of input.
+fn read_chunk(input: &mut impl Read, len: usize) -> std::io::Result<Vec<u8>> {
+    let mut buf = Vec::with_capacity(len);
+    input.take(len as u64).read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+fn encrypt_failed() -> crate::Error {
+    crate::Error::rejected(ErrorCode::EncryptionFailed, "aes-gcm stream encrypt failed")
+}
+
+fn decrypt_failed() -> crate::Error {
+    crate::Error::rejected(
+        ErrorCode::DecryptionFailed,
+        "Encrypted file is corrupted or truncated",
+    )
+}
+
+fn invalid(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InvalidInput, message)
+}
+
+fn internal(message: &str) -> crate::Error {
+    crate::Error::rejected(ErrorCode::InternalError, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    // A scratch directory under the system temp dir, removed on drop.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(test: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "tauri-plugin-biometry-stream-{test}-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&dir);
+            fs::create_dir_all(&dir).expect("scratch dir");
+            Self(dir)
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.0.join(name)
+        }
+
+        fn names(&self) -> Vec<String> {
+            let mut names: Vec<_> = fs::read_dir(&self.0)
+                .expect("read scratch dir")
+                .map(|entry| {
+                    entry
+                        .expect("entry")
+                        .file_name()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            names.sort();
+            names
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn encrypted(dek: &[u8; DEK_LEN], plaintext: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        encrypt(dek, "wrapped".to_string(), &mut &plaintext[..], &mut out).expect("encrypt");
+        out
+    }
+
+    fn decrypted(dek: &[u8; DEK_LEN], file: &[u8]) -> crate::Result<Vec<u8>> {
+        let mut input = file;
+        let opened = read_header(&mut input)?;
+        let mut out = Vec::new();
+        decrypt(dek, &opened, &mut input, &mut out)?;
+        Ok(out)
+    }
+
+    fn never_unwrap(_: &str) -> crate::Result<SecretBytes> {
+        panic!("prompted for the data key")
+    }
+
+    #[test]
+    fn round_trip_at_chunk_boundaries() {
+        let dek = new_dek();
+        for len in [0, 1, CHUNK_LEN - 1, CHUNK_LEN, CHUNK_LEN + 1, 3 * CHUNK_LEN] {
+            let plaintext: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            let file = encrypted(&dek, &plaintext);
+            assert_eq!(decrypted(&dek, &file).expect("decrypt"), plaintext, "{len}");
+        }
+    }
+
+    #[test]
+    fn tampering_fails_decryption() {
+        const SEALED: usize = CHUNK_LEN + TAG_LEN;
+        let dek = new_dek();
+        let file = encrypted(&dek, &vec![7u8; 2 * CHUNK_LEN + 10]);
+        let body = file.len() - (2 * SEALED + 10 + TAG_LEN);
+        let fails = |dek: &[u8; DEK_LEN], file: &[u8]| {
+            assert_eq!(
+                decrypted(dek, file).expect_err("decrypted").code(),
+                ErrorCode::DecryptionFailed
+            );
+        };
+
+        let mut flipped = file.clone();
+        flipped[body + 5] ^= 1;
+        fails(&dek, &flipped);
+        // Without the last chunk, the file ends on a non-final chunk.
+        fails(&dek, &file[..body + 2 * SEALED]);
+        let mut swapped = file[..body].to_vec();
+        swapped.extend_from_slice(&file[body + SEALED..body + 2 * SEALED]);
+        swapped.extend_from_slice(&file[body..body + SEALED]);
+        swapped.extend_from_slice(&file[body + 2 * SEALED..]);
+        fails(&dek, &swapped);
+        fails(&new_dek(), &file);
+    }
+
+    #[test]
+    fn failed_decryption_leaves_no_output() {
+        let scratch = Scratch::new("failed");
+        let (plain, sealed, out) = (
+            scratch.path("plain"),
+            scratch.path("sealed"),
+            scratch.path("out"),
+        );
+        fs::write(&plain, vec![1u8; CHUNK_LEN + 1]).expect("write input");
+        // Not ours: must be neither written through nor removed.
+        fs::write(scratch.path("out.partial"), b"unrelated").expect("write sibling");
+        let dek = new_dek();
+        encrypt_file(&dek, "wrapped".to_string(), &plain, &sealed).expect("encrypt");
+
+        let mut file = fs::read(&sealed).expect("read");
+        let last = file.len() - 1;
+        file[last] ^= 1;
+        fs::write(&sealed, file).expect("tamper");
+        let err = decrypt_file(&sealed, &out, |_| Ok(SecretBytes::new(dek.to_vec())))
+            .expect_err("decrypted");
+        assert_eq!(err.code(), ErrorCode::DecryptionFailed);
+        assert!(!out.exists());
+        assert_eq!(scratch.names(), ["out.partial", "plain", "sealed"]);
+        assert_eq!(
+            fs::read(scratch.path("out.partial")).expect("read sibling"),
+            b"unrelated"
+        );
+    }
+
+    #[test]
+    fn refuses_before_prompting() {
+        let scratch = Scratch::new("refuses");
+        let (plain, sealed) = (scratch.path("plain"), scratch.path("sealed"));
+        fs::write(&plain, b"not encrypted").expect("write input");
+        let dek = new_dek();
+        encrypt_file(&dek, "wrapped".to_string(), &plain, &sealed).expect("encrypt");
+
+        // Not one of ours.
+        let err = decrypt_file(&plain, &scratch.path("out"), never_unwrap).expect_err("decrypted");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+        // Output would overwrite the input, by the same or another spelling.
+        let err = decrypt_file(&sealed, &sealed, never_unwrap).expect_err("decrypted");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+        let aliased = scratch.0.join(".").join("sealed");
+        let err = decrypt_file(&sealed, &aliased, never_unwrap).expect_err("decrypted");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+        let err = encrypt_file(&dek, "wrapped".to_string(), &plain, &plain).expect_err("encrypted");
+        assert_eq!(err.code(), ErrorCode::InvalidInput);
+        assert_eq!(fs::read(&plain).expect("read"), b"not encrypted");
+    }
+}
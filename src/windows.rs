@@ -9,9 +9,11 @@ use aes_gcm::{
 use base64::{engine::general_purpose::STANDARD as B64, Engine as _};
 use rand::RngExt;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use tauri::{plugin::PluginApi, AppHandle, Runtime, WebviewWindow};
 
 use windows_future::IAsyncOperation;
+use zeroize::Zeroizing;
 
 use windows::{
     core::{factory, Error as WinError, Interface, BOOL, GUID, HRESULT, HSTRING, PCWSTR},
@@ -19,6 +21,9 @@ use windows::{
         UserConsentVerificationResult, UserConsentVerifier, UserConsentVerifierAvailability,
     },
     Security::Credentials::{PasswordCredential, PasswordVault},
+    Win32::Devices::BiometricFramework::{
+        WinBioEnumBiometricUnits, WinBioFree, WINBIO_UNIT_SCHEMA,
+    },
     Win32::Foundation::HWND,
     Win32::Networking::WindowsWebServices::{
         WebAuthNAuthenticatorGetAssertion, WebAuthNAuthenticatorMakeCredential,
@@ -40,11 +45,15 @@ use windows::{
     },
 };
 
-use crate::error::{ErrorResponse, PluginInvokeError};
+use crate::b64_field;
+use crate::encoding;
+use crate::error::{ErrorCode, ErrorResponse, PluginInvokeError};
 use crate::models::{
-    AuthOptions, BiometryType, DataOptions, DataResponse, GetDataOptions, RemoveDataOptions,
-    SetDataOptions, Status,
+    AuthOptions, BiometryStrength, BiometryType, Capabilities, DataOptions, DataResponse,
+    GetDataOptions, KeyAlgorithm, RemoveDataOptions, RenameDataOptions, SetDataOptions, Status,
 };
+use crate::payload;
+use crate::sensitive::SecretBytes;
 
 const PLUGIN_RP_PREFIX: &str = "io.tauri.plugin.biometry";
 const BLOB_VERSION: u8 = 0x01;
@@ -53,6 +62,14 @@ const AES_GCM_NONCE_LEN: usize = 12;
 const PRF_OUT_LEN: usize = 32;
 const MAX_DOMAIN_LEN: usize = 64;
 const WEBAUTHN_TIMEOUT_MS: u32 = 60_000;
+// Hashed into the PRF input for `derive_key`. Storage salts are random, so
+// this fixed input never coincides with one.
+const DERIVE_PRF_INPUT: &[u8] = b"tauri-plugin-biometry/derive-key/v1";
+// `WINBIO_TYPE_*` biometric factors from winbio_types.h, which the windows
+// crate doesn't export.
+const WINBIO_TYPE_FACIAL_FEATURES: u32 = 0x0000_0002;
+const WINBIO_TYPE_FINGERPRINT: u32 = 0x0000_0008;
+const WINBIO_TYPE_IRIS: u32 = 0x0000_0010;
 
 // Signature must match the cross-platform plugin contract — return type is
 // fixed even though Windows init can't fail.
@@ -106,21 +123,6 @@ fn u32_len(n: usize) -> Result<u32, WinError> {
 
 // -------------------- blob format --------------------
 
-mod b64_field {
-    use super::B64;
-    use base64::Engine as _;
-    use serde::{Deserialize, Deserializer, Serializer};
-
-    pub fn serialize<S: Serializer>(v: &Vec<u8>, s: S) -> Result<S::Ok, S::Error> {
-        s.serialize_str(&B64.encode(v))
-    }
-
-    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
-        let s = String::deserialize(d)?;
-        B64.decode(s.as_bytes()).map_err(serde::de::Error::custom)
-    }
-}
-
 #[derive(Serialize, Deserialize)]
 struct Blob {
     v: u8,
@@ -132,6 +134,12 @@ struct Blob {
     iv: Vec<u8>,
     #[serde(with = "b64_field")]
     ct: Vec<u8>,
+    // The credential's public key from its attestation, as an uncompressed
+    // P-256 point, for `credential_public_key`. Empty for RS256 credentials
+    // and blobs written before it was recorded. It is public, so the AAD
+    // doesn't cover it.
+    #[serde(default, with = "b64_field", skip_serializing_if = "Vec::is_empty")]
+    cpk: Vec<u8>,
 }
 
 fn decode_blob(data: &str) -> Result<Blob, String> {
@@ -348,14 +356,15 @@ use interop::IUserConsentVerifierInterop;
 
 // Creates a Hello-bound credential AND evaluates the PRF for `salt` in the
 // same operation — collapses the prior MakeCredential + GetAssertion pair
-// into one Hello prompt for first-time enrollment of a domain.
-#[allow(clippy::too_many_lines)]
+// into one Hello prompt for first-time enrollment of a domain. Returns the
+// credential id, its public key (empty unless ES256) and the PRF output.
+#[allow(clippy::too_many_lines, clippy::type_complexity)]
 fn make_webauthn_credential_with_prf(
     hwnd: HWND,
     rp_id_str: &str,
     user_label: &str,
     salt: &[u8; PRF_SALT_LEN],
-) -> Result<(Vec<u8>, [u8; PRF_OUT_LEN]), WinError> {
+) -> Result<(Vec<u8>, Vec<u8>, Zeroizing<[u8; PRF_OUT_LEN]>), WinError> {
     let rp_id_w = WideStr::new(rp_id_str);
     let rp_name_w = WideStr::new(rp_id_str);
     let rp = WEBAUTHN_RP_ENTITY_INFORMATION {
@@ -460,6 +469,19 @@ fn make_webauthn_credential_with_prf(
             std::slice::from_raw_parts(att.pbCredentialId, att.cbCredentialId as usize);
         let credential_id = cred_slice.to_vec();
 
+        // The credential public key is in the authenticator data's attested
+        // credential data. Hello picks ES256 where the authenticator
+        // supports it; an RS256 credential has no key to report.
+        let public_key = if att.pbAuthenticatorData.is_null() {
+            Vec::new()
+        } else {
+            let auth_data = std::slice::from_raw_parts(
+                att.pbAuthenticatorData,
+                att.cbAuthenticatorData as usize,
+            );
+            encoding::attested_p256_key(auth_data).unwrap_or_default()
+        };
+
         if att.pHmacSecret.is_null() {
             return Err(WinError::from(HRESULT(-1)));
         }
@@ -467,10 +489,10 @@ fn make_webauthn_credential_with_prf(
         if hmac.pbFirst.is_null() || hmac.cbFirst as usize != PRF_OUT_LEN {
             return Err(WinError::from(HRESULT(-1)));
         }
-        let mut prf_out = [0u8; PRF_OUT_LEN];
+        let mut prf_out = Zeroizing::new([0u8; PRF_OUT_LEN]);
         std::ptr::copy_nonoverlapping(hmac.pbFirst, prf_out.as_mut_ptr(), PRF_OUT_LEN);
 
-        Ok((credential_id, prf_out))
+        Ok((credential_id, public_key, prf_out))
     }
 }
 
@@ -479,7 +501,7 @@ fn get_assertion_prf(
     rp_id_str: &str,
     credential_id: &[u8],
     salt: &[u8; PRF_SALT_LEN],
-) -> Result<[u8; PRF_OUT_LEN], WinError> {
+) -> Result<Zeroizing<[u8; PRF_OUT_LEN]>, WinError> {
     let rp_id_w = WideStr::new(rp_id_str);
 
     let challenge: [u8; 32] = rand::random();
@@ -551,7 +573,7 @@ fn get_assertion_prf(
             return Err(WinError::from(HRESULT(-1)));
         }
         let slice = std::slice::from_raw_parts(secret.pbFirst, PRF_OUT_LEN);
-        let mut out = [0u8; PRF_OUT_LEN];
+        let mut out = Zeroizing::new([0u8; PRF_OUT_LEN]);
         out.copy_from_slice(slice);
         Ok(out)
     }
@@ -559,7 +581,9 @@ fn get_assertion_prf(
 
 // -------------------- PasswordVault helpers --------------------
 
-fn find_existing_credential_id_for_domain(domain: &str) -> Option<Vec<u8>> {
+// The id and recorded public key of the credential `domain`'s items are
+// sealed under.
+fn find_existing_credential_for_domain(domain: &str) -> Option<(Vec<u8>, Vec<u8>)> {
     let vault = PasswordVault::new().ok()?;
     let resource = HSTRING::from(domain);
     let entries = vault.FindAllByResource(&resource).ok()?;
@@ -573,17 +597,45 @@ fn find_existing_credential_id_for_domain(domain: &str) -> Option<Vec<u8>> {
             continue;
         };
         if let Ok(blob) = decode_blob(&password.to_string()) {
-            return Some(blob.cred);
+            return Some((blob.cred, blob.cpk));
         }
     }
     None
 }
 
+/// The kinds of biometric sensor the Windows Biometric Framework reports.
+/// Hello itself doesn't say which modality it will use, so this is the
+/// closest answer. Empty if the service isn't running or there are none.
+fn sensor_modalities() -> Vec<BiometryType> {
+    [
+        (WINBIO_TYPE_FINGERPRINT, BiometryType::Fingerprint),
+        (WINBIO_TYPE_FACIAL_FEATURES, BiometryType::Face),
+        (WINBIO_TYPE_IRIS, BiometryType::Iris),
+    ]
+    .into_iter()
+    .filter(|(factor, _)| {
+        let mut units: *mut WINBIO_UNIT_SCHEMA = ptr::null_mut();
+        let mut count = 0usize;
+        let found = unsafe { WinBioEnumBiometricUnits(*factor, &mut units, &mut count) }.is_ok()
+            && count > 0;
+        if !units.is_null() {
+            let _ = unsafe { WinBioFree(units.cast()) };
+        }
+        found
+    })
+    .map(|(_, modality)| modality)
+    .collect()
+}
+
 // -------------------- Biometry struct --------------------
 
 pub struct Biometry<R: Runtime>(AppHandle<R>);
 
 impl<R: Runtime> Biometry<R> {
+    pub(crate) const fn app(&self) -> &AppHandle<R> {
+        &self.0
+    }
+
     // Methods that don't touch self use global Windows APIs (UserConsentVerifier
     // / PasswordVault). Signatures match the cross-platform shape.
     #[allow(clippy::unused_self)]
@@ -595,6 +647,22 @@ impl<R: Runtime> Biometry<R> {
         // get_data would still fail. Probe the actual surface we use before
         // falling through to the user-visible biometry reasoning.
         let api_version = unsafe { WebAuthNGetApiVersionNumber() };
+        let platform_auth = unsafe { WebAuthNIsUserVerifyingPlatformAuthenticatorAvailable() }
+            .is_ok_and(BOOL::as_bool);
+        // Hello always offers its PIN alongside biometry, and storage and
+        // keys both need the PRF surface and a usable Hello.
+        let capabilities = |available: bool| Capabilities {
+            storage: available,
+            device_credential: platform_auth,
+            keys: available,
+            strength: if available {
+                BiometryStrength::Unknown
+            } else {
+                BiometryStrength::None
+            },
+            backend: "windowsHello".to_string(),
+            backend_version: Some(api_version.to_string()),
+        };
         if api_version < 8 {
             return Ok(Status {
                 is_available: false,
@@ -602,11 +670,11 @@ impl<R: Runtime> Biometry<R> {
                 error: Some(format!(
                     "WebAuthn API version {api_version} too old (need >= 8 for hmac-secret PRF storage)"
                 )),
-                error_code: Some("biometryNotAvailable".to_string()),
+                error_code: Some(ErrorCode::BiometryNotAvailable.to_string()),
+                modalities: Vec::new(),
+                capabilities: capabilities(false),
             });
         }
-        let platform_auth = unsafe { WebAuthNIsUserVerifyingPlatformAuthenticatorAvailable() }
-            .is_ok_and(BOOL::as_bool);
         if !platform_auth {
             return Ok(Status {
                 is_available: false,
@@ -614,47 +682,59 @@ impl<R: Runtime> Biometry<R> {
                 error: Some(
                     "WebAuthn user-verifying platform authenticator unavailable".to_string(),
                 ),
-                error_code: Some("biometryNotAvailable".to_string()),
+                error_code: Some(ErrorCode::BiometryNotAvailable.to_string()),
+                modalities: Vec::new(),
+                capabilities: capabilities(false),
             });
         }
 
         let availability = UserConsentVerifier::CheckAvailabilityAsync()
             .and_then(|async_op| async_op.get())
             .map_err(|e| {
-                reject_fmt("internalError", "Failed to check biometry availability", &e)
+                reject_fmt(
+                    ErrorCode::InternalError,
+                    "Failed to check biometry availability",
+                    &e,
+                )
             })?;
 
+        let modalities = sensor_modalities();
+        let hello_type = match modalities.as_slice() {
+            [] => BiometryType::Auto,
+            [modality] => *modality,
+            _ => BiometryType::Multiple,
+        };
         let (is_available, biometry_type, error, error_code) = match availability {
-            UserConsentVerifierAvailability::Available => (true, BiometryType::Auto, None, None),
+            UserConsentVerifierAvailability::Available => (true, hello_type, None, None),
             UserConsentVerifierAvailability::DeviceNotPresent => (
                 false,
                 BiometryType::None,
                 Some("No biometric device found".to_string()),
-                Some("biometryNotAvailable".to_string()),
+                Some(ErrorCode::BiometryNotAvailable.to_string()),
             ),
             UserConsentVerifierAvailability::NotConfiguredForUser => (
                 false,
                 BiometryType::None,
                 Some("Biometric authentication not configured".to_string()),
-                Some("biometryNotEnrolled".to_string()),
+                Some(ErrorCode::BiometryNotEnrolled.to_string()),
             ),
             UserConsentVerifierAvailability::DisabledByPolicy => (
                 false,
                 BiometryType::None,
                 Some("Biometric authentication disabled by policy".to_string()),
-                Some("biometryNotAvailable".to_string()),
+                Some(ErrorCode::BiometryNotAvailable.to_string()),
             ),
             UserConsentVerifierAvailability::DeviceBusy => (
                 false,
                 BiometryType::None,
                 Some("Biometric device is busy".to_string()),
-                Some("systemCancel".to_string()),
+                Some(ErrorCode::SystemCancel.to_string()),
             ),
             _ => (
                 false,
                 BiometryType::None,
                 Some("Unknown availability status".to_string()),
-                Some("biometryNotAvailable".to_string()),
+                Some(ErrorCode::BiometryNotAvailable.to_string()),
             ),
         };
 
@@ -663,6 +743,8 @@ impl<R: Runtime> Biometry<R> {
             biometry_type,
             error,
             error_code,
+            modalities: if is_available { modalities } else { Vec::new() },
+            capabilities: capabilities(is_available),
         })
     }
 
@@ -675,14 +757,19 @@ impl<R: Runtime> Biometry<R> {
     ) -> crate::Result<()> {
         let hwnd = window
             .hwnd()
-            .map_err(|e| reject_fmt("internalError", "resolve window hwnd", &e))?;
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "resolve window hwnd", &e))?;
 
         // Use the interop interface to parent the Hello dialog to our HWND.
         // Without this, RequestVerificationAsync's prompt appears behind the
         // app window — looks like a hang because the user can't see it.
         let interop: IUserConsentVerifierInterop =
-            factory::<UserConsentVerifier, IUserConsentVerifierInterop>()
-                .map_err(|e| reject_fmt("internalError", "get IUserConsentVerifierInterop", &e))?;
+            factory::<UserConsentVerifier, IUserConsentVerifierInterop>().map_err(|e| {
+                reject_fmt(
+                    ErrorCode::InternalError,
+                    "get IUserConsentVerifierInterop",
+                    &e,
+                )
+            })?;
 
         let message = std::mem::ManuallyDrop::new(HSTRING::from(reason));
         let mut async_op_ptr: *mut c_void = std::ptr::null_mut();
@@ -694,42 +781,58 @@ impl<R: Runtime> Biometry<R> {
                 &mut async_op_ptr,
             )
         };
-        hr.ok()
-            .map_err(|e| reject_fmt("internalError", "RequestVerificationForWindowAsync", &e))?;
+        hr.ok().map_err(|e| {
+            reject_fmt(
+                ErrorCode::InternalError,
+                "RequestVerificationForWindowAsync",
+                &e,
+            )
+        })?;
         if async_op_ptr.is_null() {
-            return Err(reject("internalError", "null IAsyncOperation pointer"));
+            return Err(reject(
+                ErrorCode::InternalError,
+                "null IAsyncOperation pointer",
+            ));
         }
         let async_op =
             unsafe { IAsyncOperation::<UserConsentVerificationResult>::from_raw(async_op_ptr) };
-        let result = async_op
-            .get()
-            .map_err(|e| reject_fmt("internalError", "Failed to request user verification", &e))?;
+        let result = async_op.get().map_err(|e| {
+            reject_fmt(
+                ErrorCode::InternalError,
+                "Failed to request user verification",
+                &e,
+            )
+        })?;
 
         match result {
             UserConsentVerificationResult::Verified => Ok(()),
             UserConsentVerificationResult::DeviceBusy => {
-                Err(reject("systemCancel", "Device is busy"))
-            }
-            UserConsentVerificationResult::DeviceNotPresent => {
-                Err(reject("biometryNotAvailable", "No biometric device found"))
+                Err(reject(ErrorCode::SystemCancel, "Device is busy"))
             }
+            UserConsentVerificationResult::DeviceNotPresent => Err(reject(
+                ErrorCode::BiometryNotAvailable,
+                "No biometric device found",
+            )),
             UserConsentVerificationResult::DisabledByPolicy => Err(reject(
-                "biometryNotAvailable",
+                ErrorCode::BiometryNotAvailable,
                 "Biometric authentication is disabled by policy",
             )),
             UserConsentVerificationResult::NotConfiguredForUser => Err(reject(
-                "biometryNotEnrolled",
+                ErrorCode::BiometryNotEnrolled,
                 "Biometric authentication is not configured for the user",
             )),
             UserConsentVerificationResult::Canceled => Err(reject(
-                "userCancel",
+                ErrorCode::UserCancel,
                 "Authentication was canceled by the user",
             )),
             UserConsentVerificationResult::RetriesExhausted => Err(reject(
-                "biometryLockout",
+                ErrorCode::BiometryLockout,
                 "Too many failed authentication attempts",
             )),
-            _ => Err(reject("authenticationFailed", "Authentication failed")),
+            _ => Err(reject(
+                ErrorCode::AuthenticationFailed,
+                "Authentication failed",
+            )),
         }
     }
 
@@ -738,7 +841,7 @@ impl<R: Runtime> Biometry<R> {
         clippy::needless_pass_by_value,
         clippy::unnecessary_wraps
     )]
-    pub fn has_data(&self, options: DataOptions) -> crate::Result<bool> {
+    pub(crate) fn backend_has_data(&self, options: DataOptions) -> crate::Result<bool> {
         let domain = options.domain;
         let name = options.name;
 
@@ -774,7 +877,7 @@ impl<R: Runtime> Biometry<R> {
     }
 
     #[allow(clippy::needless_pass_by_value)]
-    pub fn get_data(
+    pub(crate) fn backend_get_data(
         &self,
         window: WebviewWindow<R>,
         options: GetDataOptions,
@@ -783,162 +886,143 @@ impl<R: Runtime> Biometry<R> {
         let name = options.name;
 
         if domain.is_empty() || name.is_empty() {
-            return Err(reject("invalidInput", "Domain and name must not be empty"));
+            return Err(reject(
+                ErrorCode::InvalidInput,
+                "Domain and name must not be empty",
+            ));
         }
-        validate_domain(&domain).map_err(|m| reject("invalidInput", m))?;
+        validate_domain(&domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
 
         let hwnd = window
             .hwnd()
-            .map_err(|e| reject_fmt("internalError", "resolve window hwnd", &e))?;
-
-        let vault =
-            PasswordVault::new().map_err(|e| reject_fmt("internalError", "vault open", &e))?;
-        let resource = HSTRING::from(&domain);
-        let username = HSTRING::from(&name);
-
-        let credential = vault
-            .Retrieve(&resource, &username)
-            .map_err(|e| reject_fmt("dataNotFound", "vault retrieve", &e))?;
-        credential
-            .RetrievePassword()
-            .map_err(|e| reject_fmt("internalError", "retrieve password", &e))?;
-        let stored = credential
-            .Password()
-            .map_err(|e| reject_fmt("internalError", "get password", &e))?;
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "resolve window hwnd", &e))?;
 
-        let blob =
-            decode_blob(&stored.to_string()).map_err(|m| reject("dataNeedsReenrollment", &m))?;
-
-        let salt_arr: &[u8; PRF_SALT_LEN] = blob
-            .salt
-            .as_slice()
-            .try_into()
-            .map_err(|_| reject("internalError", "salt length mismatch"))?;
-
-        let rp_id_str = rp_id_for(&self.0.config().identifier, &domain);
-
-        let prf_out = get_assertion_prf(hwnd, &rp_id_str, &blob.cred, salt_arr)
-            .map_err(|e| reject_fmt("authenticationFailed", "webauthn assertion", &e))?;
-
-        let cipher = Aes256Gcm::new_from_slice(&prf_out)
-            .map_err(|e| reject("internalError", &format!("aes key init: {e}")))?;
-        let nonce = Nonce::from_slice(&blob.iv);
-        let aad = aad_for(&domain, &name, &blob.salt, &blob.cred)
-            .map_err(|e| reject("internalError", &format!("aad: {e}")))?;
-        let plaintext = cipher
-            .decrypt(
-                nonce,
-                Payload {
-                    msg: &blob.ct,
-                    aad: &aad,
-                },
-            )
-            .map_err(|e| reject("decryptionFailed", &format!("aes-gcm decrypt: {e}")))?;
+        let (_, _, plaintext) = self.open_record(hwnd, &domain, &name)?;
 
-        let data_string = String::from_utf8(plaintext)
-            .map_err(|e| reject("internalError", &format!("utf-8: {e}")))?;
+        let data = std::str::from_utf8(&plaintext)
+            .map_err(|e| reject(ErrorCode::InternalError, &format!("utf-8: {e}")))?;
 
-        Ok(DataResponse {
-            domain,
-            name,
-            data: data_string,
-        })
+        Ok(payload::response(domain, name, data.into()))
     }
 
     #[allow(clippy::needless_pass_by_value)]
-    pub fn set_data(&self, window: WebviewWindow<R>, options: SetDataOptions) -> crate::Result<()> {
+    pub(crate) fn backend_set_data(
+        &self,
+        window: WebviewWindow<R>,
+        options: SetDataOptions,
+    ) -> crate::Result<()> {
+        let data = payload::stored(&options);
         let domain = options.domain;
         let name = options.name;
-        let data = options.data;
 
         if domain.is_empty() || name.is_empty() {
-            return Err(reject("invalidInput", "Domain and name must not be empty"));
+            return Err(reject(
+                ErrorCode::InvalidInput,
+                "Domain and name must not be empty",
+            ));
+        }
+        validate_domain(&domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
+
+        // Checked before the Hello prompt so a create-only write that is
+        // bound to fail doesn't ask the user for a biometric first.
+        if options.if_absent == Some(true) && vault_contains(&domain, &name) {
+            return Err(reject(ErrorCode::Conflict, "Item already exists"));
         }
-        validate_domain(&domain).map_err(|m| reject("invalidInput", m))?;
 
         let hwnd = window
             .hwnd()
-            .map_err(|e| reject_fmt("internalError", "resolve window hwnd", &e))?;
-
-        let rp_id_str = rp_id_for(&self.0.config().identifier, &domain);
-
-        let mut salt = [0u8; PRF_SALT_LEN];
-        rand::rng().fill(&mut salt);
-        let mut iv = [0u8; AES_GCM_NONCE_LEN];
-        rand::rng().fill(&mut iv);
-
-        let (credential_id, prf_out) = match find_existing_credential_id_for_domain(&domain) {
-            Some(id) => {
-                let prf = get_assertion_prf(hwnd, &rp_id_str, &id, &salt)
-                    .map_err(|e| reject_fmt("authenticationFailed", "webauthn assertion", &e))?;
-                (id, prf)
-            }
-            None => {
-                make_webauthn_credential_with_prf(hwnd, &rp_id_str, &name, &salt).map_err(|e| {
-                    reject_fmt("credentialCreationFailed", "webauthn make credential", &e)
-                })?
-            }
-        };
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "resolve window hwnd", &e))?;
 
-        let cipher = Aes256Gcm::new_from_slice(&prf_out)
-            .map_err(|e| reject("internalError", &format!("aes key init: {e}")))?;
-        let nonce = Nonce::from_slice(&iv);
-        let aad = aad_for(&domain, &name, &salt, &credential_id)
-            .map_err(|e| reject("internalError", &format!("aad: {e}")))?;
-        let ciphertext_with_tag = cipher
-            .encrypt(
-                nonce,
-                Payload {
-                    msg: data.as_bytes(),
-                    aad: &aad,
-                },
-            )
-            .map_err(|e| reject("encryptionFailed", &format!("aes-gcm encrypt: {e}")))?;
-
-        let blob = Blob {
-            v: BLOB_VERSION,
-            cred: credential_id,
-            salt: salt.to_vec(),
-            iv: iv.to_vec(),
-            ct: ciphertext_with_tag,
-        };
-        let stored = serde_json::to_string(&blob)
-            .map_err(|e| reject("internalError", &format!("encode blob: {e}")))?;
+        let stored = self.seal_record(hwnd, &domain, &name, data.as_bytes())?;
+        vault_put(&domain, &name, &stored)
+    }
 
-        let vault =
-            PasswordVault::new().map_err(|e| reject_fmt("internalError", "vault open", &e))?;
-        let resource = HSTRING::from(&domain);
-        let username = HSTRING::from(&name);
-        let password = HSTRING::from(&stored);
+    #[allow(clippy::needless_pass_by_value)]
+    pub(crate) fn backend_rename_data(
+        &self,
+        window: WebviewWindow<R>,
+        options: RenameDataOptions,
+    ) -> crate::Result<()> {
+        let RenameDataOptions {
+            domain,
+            name,
+            new_domain,
+            new_name,
+            ..
+        } = options;
+
+        if domain.is_empty() || name.is_empty() || new_domain.is_empty() || new_name.is_empty() {
+            return Err(reject(
+                ErrorCode::InvalidInput,
+                "Domain and name must not be empty",
+            ));
+        }
+        validate_domain(&domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
+        validate_domain(&new_domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
 
-        if let Ok(existing) = vault.Retrieve(&resource, &username) {
-            let _ = vault.Remove(&existing);
+        if vault_contains(&new_domain, &new_name) {
+            return Err(reject(ErrorCode::Conflict, "Target item already exists"));
         }
 
-        let cred = PasswordCredential::CreatePasswordCredential(&resource, &username, &password)
-            .map_err(|e| reject_fmt("internalError", "create password credential", &e))?;
-        vault
-            .Add(&cred)
-            .map_err(|e| reject_fmt("internalError", "vault add", &e))?;
+        let hwnd = window
+            .hwnd()
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "resolve window hwnd", &e))?;
+
+        // The AAD binds every blob to its (domain, name), so the payload has
+        // to be re-sealed — in Rust, never handed back to the webview.
+        // Within one domain the WebAuthn credential and salt stay the same,
+        // so the PRF output from the unseal is reused and the user sees a
+        // single Hello prompt. Moving across domains needs the target
+        // domain's credential, which costs a second prompt.
+        let (blob, key, plaintext) = self.open_record(hwnd, &domain, &name)?;
+        let stored = if new_domain == domain {
+            seal_with_key(
+                &new_domain,
+                &new_name,
+                blob.cred,
+                blob.cpk,
+                blob.salt,
+                &key,
+                &plaintext,
+            )?
+        } else {
+            self.seal_record(hwnd, &new_domain, &new_name, &plaintext)?
+        };
 
+        // Target first, source second. If writing the target fails, nothing
+        // has changed. `backend_remove_data` only fails before the source's
+        // vault entry is gone, so if removing the source fails, the target
+        // is removed again and the source is left as the only copy. If that
+        // cleanup fails too, both copies remain. The item is never lost.
+        vault_put(&new_domain, &new_name, &stored)?;
+        if let Err(e) = self.backend_remove_data(RemoveDataOptions { domain, name }) {
+            let _ = self.backend_remove_data(RemoveDataOptions {
+                domain: new_domain,
+                name: new_name,
+            });
+            return Err(e);
+        }
         Ok(())
     }
 
     #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
-    pub fn remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
+    pub(crate) fn backend_remove_data(&self, options: RemoveDataOptions) -> crate::Result<()> {
         let domain = options.domain;
         let name = options.name;
 
         if domain.is_empty() || name.is_empty() {
-            return Err(reject("invalidInput", "Domain and name must not be empty"));
+            return Err(reject(
+                ErrorCode::InvalidInput,
+                "Domain and name must not be empty",
+            ));
         }
         // Same validation as set_data / get_data so a caller with remove_data
         // permission can't delete vault entries outside the plugin's intended
         // domain shape.
-        validate_domain(&domain).map_err(|m| reject("invalidInput", m))?;
+        validate_domain(&domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
 
-        let vault =
-            PasswordVault::new().map_err(|e| reject_fmt("internalError", "vault open", &e))?;
+        let vault = PasswordVault::new()
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "vault open", &e))?;
         let resource = HSTRING::from(&domain);
         let username = HSTRING::from(&name);
 
@@ -959,7 +1043,7 @@ impl<R: Runtime> Biometry<R> {
 
         vault
             .Remove(&cred)
-            .map_err(|e| reject_fmt("internalError", "vault remove", &e))?;
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "vault remove", &e))?;
 
         // If no other (domain, name) entries reference this credential,
         // remove the platform credential too so it doesn't linger in
@@ -979,9 +1063,309 @@ impl<R: Runtime> Biometry<R> {
 
         Ok(())
     }
+
+    // Hello's key store (`KeyCredentialManager`) only offers RSA keys, so
+    // every key is generated in Rust and sealed under the domain's WebAuthn
+    // PRF like any other item. The other `platform_*` methods are never
+    // reached.
+    #[allow(clippy::unused_self, clippy::unnecessary_wraps)]
+    pub(crate) fn create_platform_key(
+        &self,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _name: &str,
+        _alg: KeyAlgorithm,
+    ) -> crate::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn platform_sign(
+        &self,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _name: &str,
+        _payload: &[u8],
+        _reason: String,
+        _cancel_title: Option<String>,
+    ) -> crate::Result<Vec<u8>> {
+        Err(no_platform_keys())
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn platform_key_agreement(
+        &self,
+        _window: WebviewWindow<R>,
+        _domain: &str,
+        _name: &str,
+        _peer: &[u8],
+        _reason: String,
+        _cancel_title: Option<String>,
+    ) -> crate::Result<SecretBytes> {
+        Err(no_platform_keys())
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn delete_platform_key(&self, _domain: &str, _name: &str) -> crate::Result<()> {
+        Err(no_platform_keys())
+    }
+}
+
+impl<R: Runtime> Biometry<R> {
+    /// The public key of the `WebAuthn` credential `domain`'s items are
+    /// sealed under, as an uncompressed P-256 point, read from the
+    /// attestation recorded when the credential was created. Does not
+    /// prompt.
+    #[allow(clippy::unused_self)]
+    pub fn credential_public_key(&self, domain: &str) -> crate::Result<Vec<u8>> {
+        validate_domain(domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
+        let (_, public_key) = find_existing_credential_for_domain(domain)
+            .ok_or_else(|| reject(ErrorCode::ItemNotFound, "No credential for this domain"))?;
+        if public_key.is_empty() {
+            return Err(reject(
+                ErrorCode::NotSupported,
+                "The credential's public key was not recorded: it is not an ES256 key, or it was created by an earlier version",
+            ));
+        }
+        Ok(public_key)
+    }
+
+    /// Evaluates the PRF of `domain`'s WebAuthn credential at a fixed input,
+    /// prompting Hello once. The output is stable for as long as the
+    /// credential exists. On first use the credential is pinned by sealing an
+    /// empty record at `root_name`, so it survives the domain's items being
+    /// removed.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn prf_root(
+        &self,
+        window: WebviewWindow<R>,
+        domain: &str,
+        root_name: &str,
+    ) -> crate::Result<Zeroizing<[u8; PRF_OUT_LEN]>> {
+        if domain.is_empty() {
+            return Err(reject(ErrorCode::InvalidInput, "Domain must not be empty"));
+        }
+        validate_domain(domain).map_err(|m| reject(ErrorCode::InvalidInput, m))?;
+
+        let hwnd = window
+            .hwnd()
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "resolve window hwnd", &e))?;
+
+        let stored = match vault_get(domain, root_name) {
+            Some(stored) => stored,
+            None => {
+                let stored = self.seal_record(hwnd, domain, root_name, &[])?;
+                vault_put(domain, root_name, &stored)?;
+                stored
+            }
+        };
+        let blob =
+            decode_blob(&stored).map_err(|m| reject(ErrorCode::DataNeedsReenrollment, &m))?;
+
+        let salt: [u8; PRF_SALT_LEN] = Sha256::digest(DERIVE_PRF_INPUT).into();
+        let rp_id_str = rp_id_for(&self.0.config().identifier, domain);
+        get_assertion_prf(hwnd, &rp_id_str, &blob.cred, &salt)
+            .map_err(|e| reject_fmt(ErrorCode::AuthenticationFailed, "webauthn assertion", &e))
+    }
+
+    // Reads and decrypts the vault entry for `(domain, name)`, prompting
+    // Hello once for the PRF evaluation. The decoded blob and PRF output
+    // come back alongside the plaintext so a caller re-sealing under the
+    // same credential can skip a second prompt.
+    fn open_record(
+        &self,
+        hwnd: HWND,
+        domain: &str,
+        name: &str,
+    ) -> crate::Result<(Blob, Zeroizing<[u8; PRF_OUT_LEN]>, Zeroizing<Vec<u8>>)> {
+        let vault = PasswordVault::new()
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "vault open", &e))?;
+        let resource = HSTRING::from(domain);
+        let username = HSTRING::from(name);
+
+        let credential = vault
+            .Retrieve(&resource, &username)
+            .map_err(|e| reject_fmt(ErrorCode::ItemNotFound, "vault retrieve", &e))?;
+        credential
+            .RetrievePassword()
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "retrieve password", &e))?;
+        let stored = credential
+            .Password()
+            .map_err(|e| reject_fmt(ErrorCode::InternalError, "get password", &e))?;
+
+        let blob = decode_blob(&stored.to_string())
+            .map_err(|m| reject(ErrorCode::DataNeedsReenrollment, &m))?;
+
+        let salt_arr: &[u8; PRF_SALT_LEN] = blob
+            .salt
+            .as_slice()
+            .try_into()
+            .map_err(|_| reject(ErrorCode::InternalError, "salt length mismatch"))?;
+
+        let rp_id_str = rp_id_for(&self.0.config().identifier, domain);
+
+        let prf_out = get_assertion_prf(hwnd, &rp_id_str, &blob.cred, salt_arr)
+            .map_err(|e| reject_fmt(ErrorCode::AuthenticationFailed, "webauthn assertion", &e))?;
+
+        let cipher = Aes256Gcm::new_from_slice(prf_out.as_slice())
+            .map_err(|e| reject(ErrorCode::InternalError, &format!("aes key init: {e}")))?;
+        let nonce = Nonce::from_slice(&blob.iv);
+        let aad = aad_for(domain, name, &blob.salt, &blob.cred)
+            .map_err(|e| reject(ErrorCode::InternalError, &format!("aad: {e}")))?;
+        let plaintext = cipher
+            .decrypt(
+                nonce,
+                Payload {
+                    msg: &blob.ct,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| {
+                reject(
+                    ErrorCode::DecryptionFailed,
+                    &format!("aes-gcm decrypt: {e}"),
+                )
+            })?;
+
+        Ok((blob, prf_out, Zeroizing::new(plaintext)))
+    }
+
+    // Seals `plaintext` for `(domain, name)` under the domain's WebAuthn
+    // credential — enrolling one on first use — with a fresh PRF salt, and
+    // returns the serialized blob ready for the vault.
+    fn seal_record(
+        &self,
+        hwnd: HWND,
+        domain: &str,
+        name: &str,
+        plaintext: &[u8],
+    ) -> crate::Result<String> {
+        let rp_id_str = rp_id_for(&self.0.config().identifier, domain);
+
+        let mut salt = [0u8; PRF_SALT_LEN];
+        rand::rng().fill(&mut salt);
+
+        let (credential_id, credential_key, prf_out) =
+            match find_existing_credential_for_domain(domain) {
+                Some((id, public_key)) => {
+                    let prf = get_assertion_prf(hwnd, &rp_id_str, &id, &salt).map_err(|e| {
+                        reject_fmt(ErrorCode::AuthenticationFailed, "webauthn assertion", &e)
+                    })?;
+                    (id, public_key, prf)
+                }
+                None => make_webauthn_credential_with_prf(hwnd, &rp_id_str, name, &salt).map_err(
+                    |e| {
+                        reject_fmt(
+                            ErrorCode::CredentialCreationFailed,
+                            "webauthn make credential",
+                            &e,
+                        )
+                    },
+                )?,
+            };
+
+        seal_with_key(
+            domain,
+            name,
+            credential_id,
+            credential_key,
+            salt.to_vec(),
+            &prf_out,
+            plaintext,
+        )
+    }
+}
+
+// AES-GCM half of `seal_record`, split out so a rename within one domain
+// can reuse the PRF output it already holds. Always draws a fresh IV.
+fn seal_with_key(
+    domain: &str,
+    name: &str,
+    credential_id: Vec<u8>,
+    credential_key: Vec<u8>,
+    salt: Vec<u8>,
+    key: &[u8; PRF_OUT_LEN],
+    plaintext: &[u8],
+) -> crate::Result<String> {
+    let mut iv = [0u8; AES_GCM_NONCE_LEN];
+    rand::rng().fill(&mut iv);
+
+    let cipher = Aes256Gcm::new_from_slice(key)
+        .map_err(|e| reject(ErrorCode::InternalError, &format!("aes key init: {e}")))?;
+    let nonce = Nonce::from_slice(&iv);
+    let aad = aad_for(domain, name, &salt, &credential_id)
+        .map_err(|e| reject(ErrorCode::InternalError, &format!("aad: {e}")))?;
+    let ciphertext_with_tag = cipher
+        .encrypt(
+            nonce,
+            Payload {
+                msg: plaintext,
+                aad: &aad,
+            },
+        )
+        .map_err(|e| {
+            reject(
+                ErrorCode::EncryptionFailed,
+                &format!("aes-gcm encrypt: {e}"),
+            )
+        })?;
+
+    let blob = Blob {
+        v: BLOB_VERSION,
+        cred: credential_id,
+        salt,
+        iv: iv.to_vec(),
+        ct: ciphertext_with_tag,
+        cpk: credential_key,
+    };
+    serde_json::to_string(&blob)
+        .map_err(|e| reject(ErrorCode::InternalError, &format!("encode blob: {e}")))
+}
+
+// Reads a vault entry's password without prompting.
+fn vault_get(domain: &str, name: &str) -> Option<String> {
+    let credential = PasswordVault::new()
+        .and_then(|v| v.Retrieve(&HSTRING::from(domain), &HSTRING::from(name)))
+        .ok()?;
+    credential.RetrievePassword().ok()?;
+    credential.Password().ok().map(|p| p.to_string())
+}
+
+fn vault_contains(domain: &str, name: &str) -> bool {
+    PasswordVault::new()
+        .and_then(|v| v.Retrieve(&HSTRING::from(domain), &HSTRING::from(name)))
+        .is_ok()
+}
+
+// Replaces any existing entry: PasswordVault has no in-place update.
+fn vault_put(domain: &str, name: &str, stored: &str) -> crate::Result<()> {
+    let vault =
+        PasswordVault::new().map_err(|e| reject_fmt(ErrorCode::InternalError, "vault open", &e))?;
+    let resource = HSTRING::from(domain);
+    let username = HSTRING::from(name);
+    let password = HSTRING::from(stored);
+
+    if let Ok(existing) = vault.Retrieve(&resource, &username) {
+        let _ = vault.Remove(&existing);
+    }
+
+    let cred = PasswordCredential::CreatePasswordCredential(&resource, &username, &password)
+        .map_err(|e| reject_fmt(ErrorCode::InternalError, "create password credential", &e))?;
+    vault
+        .Add(&cred)
+        .map_err(|e| reject_fmt(ErrorCode::InternalError, "vault add", &e))?;
+
+    Ok(())
+}
+
+fn no_platform_keys() -> crate::Error {
+    reject(
+        ErrorCode::NotSupported,
+        "Platform keys are not available on Windows",
+    )
 }
 
-fn reject(code: &str, message: &str) -> crate::Error {
+fn reject(code: ErrorCode, message: &str) -> crate::Error {
     crate::Error::PluginInvoke(PluginInvokeError::InvokeRejected(ErrorResponse {
         code: Some(code.to_string()),
         message: Some(message.to_string()),
@@ -989,6 +1373,6 @@ fn reject(code: &str, message: &str) -> crate::Error {
     }))
 }
 
-fn reject_fmt<E: std::fmt::Debug>(code: &str, ctx: &str, err: &E) -> crate::Error {
+fn reject_fmt<E: std::fmt::Debug>(code: ErrorCode, ctx: &str, err: &E) -> crate::Error {
     reject(code, &format!("{ctx}: {err:?}"))
 }